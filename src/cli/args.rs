@@ -46,6 +46,18 @@ pub struct Args {
     #[arg(long)]
     pub enf: bool,
 
+    /// Enable detection of gated / noise-reduced noise floors
+    #[arg(long = "noise-floor")]
+    pub noise_floor: bool,
+
+    /// Enable detection of narrow notches (spectral holes) in the passband
+    #[arg(long = "spectral-holes")]
+    pub spectral_holes: bool,
+
+    /// Analyze in segments of this many seconds and report a verdict for each
+    #[arg(long = "segment-duration", value_name = "SECONDS", value_parser = parse_segment_duration)]
+    pub segment_duration: Option<f64>,
+
     /// Enable MFCC-based codec artifact / lossy transcode detection
     #[arg(long, default_value_t = true)]
     pub mfcc: bool,
@@ -54,6 +66,15 @@ pub struct Args {
     #[arg(long)]
     pub spectrogram: bool,
 
+    /// Write the verdict into the file's metadata as Vorbis comments.
+    /// FLAC only: other formats are analyzed but not tagged
+    #[arg(long = "write-tags")]
+    pub write_tags: bool,
+
+    /// Remove previously written AUDIOCHECKR_* tags instead of analyzing (FLAC only)
+    #[arg(long = "remove-tags", conflicts_with = "write_tags")]
+    pub remove_tags: bool,
+
     /// Export detailed report to file
     #[arg(long)]
     pub report: Option<PathBuf>,
@@ -104,6 +125,18 @@ pub enum GenreProfile {
     Acoustic,
 }
 
+/// Segment length must be a positive, finite number of seconds
+fn parse_segment_duration(s: &str) -> Result<f64, String> {
+    let secs: f64 = s
+        .parse()
+        .map_err(|_| format!("`{}` is not a number of seconds", s))?;
+    if secs.is_finite() && secs > 0.0 {
+        Ok(secs)
+    } else {
+        Err(format!("must be a positive number of seconds, got `{}`", s))
+    }
+}
+
 impl Default for Args {
     fn default() -> Self {
         Self {
@@ -116,8 +149,13 @@ impl Default for Args {
             mqa: false,
             clipping: false,
             enf: false,
+            noise_floor: false,
+            spectral_holes: false,
+            segment_duration: None,
             mfcc: true,
             spectrogram: false,
+            write_tags: false,
+            remove_tags: false,
             report: None,
             min_confidence: 0.5,
             genre: None,
@@ -0,0 +1,286 @@
+//! Encoder fingerprinting module
+//!
+//! Tries to name the encoder behind a lossy transcode from the shape of
+//! the averaged spectrum around and below its cutoff. Two features are
+//! measured:
+//!
+//! - **Subband ripple** — MP3's 32-band polyphase filterbank leaves a
+//!   periodic ripple with a period of `sample_rate / 64` Hz across the
+//!   passband. AAC's MDCT has no such lattice.
+//! - **Rolloff width** — the distance from 10 dB to 40 dB below the
+//!   passband. Fraunhofer encoders cut hard; LAME and iTunes AAC apply a
+//!   lowpass with a transition band.
+//!
+//! This is heuristic and deliberately conservative: ambiguous spectra
+//! return no fingerprint rather than a guess.
+
+use std::fmt;
+
+/// Encoder families that can be told apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoder {
+    Lame,
+    Fraunhofer,
+    ITunesAac,
+}
+
+impl Encoder {
+    /// Codec this encoder produces, as in `DefectType::codec_name`
+    pub fn codec_name(&self) -> &'static str {
+        match self {
+            Encoder::Lame | Encoder::Fraunhofer => "MP3",
+            Encoder::ITunesAac => "AAC",
+        }
+    }
+}
+
+impl fmt::Display for Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoder::Lame => write!(f, "LAME"),
+            Encoder::Fraunhofer => write!(f, "Fraunhofer"),
+            Encoder::ITunesAac => write!(f, "iTunes AAC"),
+        }
+    }
+}
+
+/// Identified encoder and the features that led to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncoderFingerprint {
+    pub encoder: Encoder,
+    /// Amplitude of the polyphase subband ripple (dB)
+    pub subband_ripple_db: f64,
+    /// Width of the rolloff from -10 dB to -40 dB (Hz)
+    pub rolloff_width_hz: f64,
+}
+
+impl fmt::Display for EncoderFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "likely {} encoder (subband ripple {:.1} dB, {:.0} Hz rolloff)",
+            self.encoder, self.subband_ripple_db, self.rolloff_width_hz
+        )
+    }
+}
+
+/// Encoder fingerprint analyzer
+pub struct EncoderFingerprinter {
+    /// Ripple at or above this marks an MP3 filterbank (dB)
+    min_mp3_ripple_db: f64,
+    /// Ripple at or below this marks an MDCT-only codec (dB)
+    max_aac_ripple_db: f64,
+    /// Rolloffs narrower than this count as a hard brickwall (Hz)
+    brickwall_max_hz: f64,
+    /// Lowest frequency used for the ripple measurement (Hz)
+    ripple_lo_hz: f64,
+}
+
+impl Default for EncoderFingerprinter {
+    fn default() -> Self {
+        Self {
+            min_mp3_ripple_db: 0.75,
+            max_aac_ripple_db: 0.3,
+            brickwall_max_hz: 150.0,
+            ripple_lo_hz: 2_000.0,
+        }
+    }
+}
+
+impl EncoderFingerprinter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_min_mp3_ripple(mut self, ripple_db: f64) -> Self {
+        self.min_mp3_ripple_db = ripple_db;
+        self
+    }
+
+    pub fn with_brickwall_width(mut self, width_hz: f64) -> Self {
+        self.brickwall_max_hz = width_hz;
+        self
+    }
+
+    /// Identify the encoder from `spectrum_db` (bins 0..=Nyquist) given the
+    /// detected lossy cutoff.
+    pub fn identify(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        cutoff_hz: f64,
+    ) -> Option<EncoderFingerprint> {
+        let ripple = self.subband_ripple_db(spectrum_db, sample_rate, cutoff_hz)?;
+        let rolloff = self.rolloff_width_hz(spectrum_db, sample_rate, cutoff_hz)?;
+        let brickwall = rolloff <= self.brickwall_max_hz;
+
+        let encoder = if ripple >= self.min_mp3_ripple_db {
+            if brickwall {
+                Encoder::Fraunhofer
+            } else {
+                Encoder::Lame
+            }
+        } else if ripple <= self.max_aac_ripple_db && !brickwall {
+            Encoder::ITunesAac
+        } else {
+            return None;
+        };
+
+        Some(EncoderFingerprint {
+            encoder,
+            subband_ripple_db: ripple,
+            rolloff_width_hz: rolloff,
+        })
+    }
+
+    /// Amplitude of the `sample_rate / 64` Hz periodic component of the
+    /// passband, after removing the spectral trend.
+    pub fn subband_ripple_db(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        cutoff_hz: f64,
+    ) -> Option<f64> {
+        if spectrum_db.len() < 256 {
+            return None;
+        }
+
+        let bin_hz = sample_rate as f64 / 2.0 / (spectrum_db.len() - 1) as f64;
+        let period_bins = sample_rate as f64 / 64.0 / bin_hz;
+        let half = (period_bins / 2.0).round() as usize;
+
+        let lo = ((self.ripple_lo_hz / bin_hz).ceil() as usize).max(half);
+        let hi = (((cutoff_hz - 500.0) / bin_hz).floor() as usize)
+            .min(spectrum_db.len().saturating_sub(half + 1));
+        if hi <= lo || ((hi - lo) as f64) < period_bins * 4.0 {
+            return None;
+        }
+
+        let clamped: Vec<f64> = spectrum_db.iter().map(|&db| db.max(-150.0)).collect();
+
+        // A moving average one period wide cancels the ripple itself, so
+        // subtracting it leaves the ripple and drops the trend.
+        let (mut re, mut im) = (0.0, 0.0);
+        for i in lo..hi {
+            let window = &clamped[i - half..=i + half];
+            let trend = window.iter().sum::<f64>() / window.len() as f64;
+            let residual = clamped[i] - trend;
+            let phase = 2.0 * std::f64::consts::PI * i as f64 / period_bins;
+            re += residual * phase.cos();
+            im += residual * phase.sin();
+        }
+
+        Some(2.0 * (re * re + im * im).sqrt() / (hi - lo) as f64)
+    }
+
+    /// Width of the rolloff from 10 dB to 40 dB below the passband level
+    /// just under the cutoff.
+    pub fn rolloff_width_hz(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        cutoff_hz: f64,
+    ) -> Option<f64> {
+        if spectrum_db.len() < 256 {
+            return None;
+        }
+
+        let bin_hz = sample_rate as f64 / 2.0 / (spectrum_db.len() - 1) as f64;
+        let to_bin = |hz: f64| ((hz / bin_hz).round().max(0.0) as usize).min(spectrum_db.len() - 1);
+
+        let pass_lo = to_bin(cutoff_hz - 2_000.0);
+        let search_lo = to_bin(cutoff_hz - 1_000.0);
+        let search_hi = to_bin(cutoff_hz + 2_000.0);
+        if search_lo <= pass_lo || search_hi <= search_lo {
+            return None;
+        }
+
+        let mut passband = spectrum_db[pass_lo..search_lo].to_vec();
+        passband.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let level = passband[passband.len() / 2];
+
+        // Light smoothing so single noisy bins don't end the search early
+        let smoothed = |i: usize| {
+            let lo = i.saturating_sub(4);
+            let hi = (i + 5).min(spectrum_db.len());
+            spectrum_db[lo..hi].iter().sum::<f64>() / (hi - lo) as f64
+        };
+
+        let start = (search_lo..search_hi).find(|&i| smoothed(i) < level - 10.0)?;
+        let end = (start..search_hi).find(|&i| smoothed(i) < level - 40.0)?;
+
+        Some((end - start) as f64 * bin_hz)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const SR: u32 = 44100;
+    pub(crate) const BINS: usize = 4097; // fft_size 8192
+    const CUTOFF: f64 = 16_000.0;
+
+    /// Sloping averaged spectrum with an optional subband ripple and a
+    /// linear 60 dB rolloff of the given width centred on `cutoff_hz`.
+    /// Shared with the spectral hole and detector tests.
+    pub(crate) fn synthetic_spectrum(ripple_db: f64, cutoff_hz: f64, rolloff_hz: f64) -> Vec<f64> {
+        let bin_hz = SR as f64 / 2.0 / (BINS - 1) as f64;
+        let period = SR as f64 / 64.0;
+        let knee = cutoff_hz - rolloff_hz / 2.0;
+        (0..BINS)
+            .map(|i| {
+                let f = (i as f64 * bin_hz).max(20.0);
+                let mut db = -40.0 - 3.0 * (f / 1_000.0).log2();
+                db += ripple_db * (2.0 * std::f64::consts::PI * f / period).cos();
+                if f > knee {
+                    db -= (60.0 * (f - knee) / rolloff_hz).min(60.0);
+                }
+                db
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lame_and_itunes_signatures_differ() {
+        let fp = EncoderFingerprinter::new();
+
+        let lame = fp
+            .identify(&synthetic_spectrum(2.0, CUTOFF, 1_000.0), SR, CUTOFF)
+            .expect("Rippled spectrum with gentle rolloff should be identified");
+        assert_eq!(lame.encoder, Encoder::Lame);
+
+        let itunes = fp
+            .identify(&synthetic_spectrum(0.0, CUTOFF, 1_000.0), SR, CUTOFF)
+            .expect("Ripple-free spectrum with gentle rolloff should be identified");
+        assert_eq!(itunes.encoder, Encoder::ITunesAac);
+
+        assert!(
+            lame.subband_ripple_db > 1.5,
+            "ripple {:.2}",
+            lame.subband_ripple_db
+        );
+        assert!(
+            itunes.subband_ripple_db < 0.1,
+            "ripple {:.2}",
+            itunes.subband_ripple_db
+        );
+    }
+
+    #[test]
+    fn test_brickwall_mp3_is_fraunhofer() {
+        let fp = EncoderFingerprinter::new()
+            .identify(&synthetic_spectrum(2.0, CUTOFF, 60.0), SR, CUTOFF)
+            .expect("Rippled brickwall spectrum should be identified");
+        assert_eq!(fp.encoder, Encoder::Fraunhofer);
+        assert!(fp.rolloff_width_hz <= 150.0);
+    }
+
+    #[test]
+    fn test_ambiguous_ripple_not_identified() {
+        let fp = EncoderFingerprinter::new();
+        assert!(fp
+            .identify(&synthetic_spectrum(0.5, CUTOFF, 1_000.0), SR, CUTOFF)
+            .is_none());
+    }
+}
@@ -3,14 +3,20 @@
 pub mod clipping_detection;
 pub mod dithering_detection;
 pub mod dynamic_range;
+pub mod encoder_fingerprint;
 pub mod mfcc;
 pub mod mqa_detection;
+pub mod noise_floor_detection;
+pub mod null_compare;
 pub mod resampling_detection;
+pub mod spectral_hole_detection;
+pub mod summary;
 
 pub use dynamic_range::{DynamicRangeAnalyzer, DynamicRangeResult, DynamicRangeVerdict};
 pub use mfcc::{MfccAnalyzer, MfccConfig, MfccFingerprint, MfccResult};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+pub use summary::Verbosity;
 
 /// Configuration for audio analysis
 #[derive(Debug, Clone)]
@@ -21,6 +27,11 @@ pub struct AnalysisConfig {
     pub enable_mqa: bool,
     pub enable_clipping: bool,
     pub enable_enf: bool,
+    pub enable_noise_floor: bool,
+    pub enable_spectral_holes: bool,
+    /// Split the file into segments of this many seconds and give each a
+    /// verdict; `None` analyzes the whole file only
+    pub segment_duration: Option<f64>,
     pub genre_profile: Option<String>,
     pub sensitivity: AnalysisSensitivity,
     pub enable_mfcc: bool,
@@ -35,6 +46,9 @@ impl Default for AnalysisConfig {
             enable_mqa: false,
             enable_clipping: false,
             enable_enf: false,
+            enable_noise_floor: false,
+            enable_spectral_holes: false,
+            segment_duration: None,
             genre_profile: None,
             sensitivity: AnalysisSensitivity::Medium,
             enable_mfcc: true,
@@ -64,19 +78,41 @@ pub struct AnalysisResult {
     pub analysis_timestamp: String,
     pub dynamic_range: Option<DynamicRangeResult>,
     pub mfcc: Option<MfccResult>,
+    /// Per-segment verdicts (empty unless segment analysis was requested)
+    #[serde(default)]
+    pub segments: Vec<SegmentVerdict>,
 }
 
 impl AnalysisResult {
     /// Returns true if the file appears to be genuine lossless
     pub fn is_genuine(&self) -> bool {
-        self.detections.is_empty()
-            || self
-                .detections
-                .iter()
-                .all(|d| d.severity == Severity::Info || d.severity == Severity::Low)
+        all_benign(&self.detections)
     }
 }
 
+/// Verdict for one fixed-length segment of a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentVerdict {
+    pub index: usize,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub detections: Vec<Detection>,
+    pub confidence: f64,
+}
+
+impl SegmentVerdict {
+    /// Returns true if this segment appears to be genuine lossless
+    pub fn is_genuine(&self) -> bool {
+        all_benign(&self.detections)
+    }
+}
+
+fn all_benign(detections: &[Detection]) -> bool {
+    detections
+        .iter()
+        .all(|d| d.severity == Severity::Info || d.severity == Severity::Low)
+}
+
 /// A single detection/finding from the analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Detection {
@@ -168,6 +204,18 @@ pub enum DefectType {
         integrated_lufs: f64,
         plr_db: f64,
     },
+    /// Noise floor gated or reduced below what the bit depth supports
+    NoiseReduction {
+        noise_floor_db: f64,
+        bit_depth_floor_db: f64,
+        gated_regions: u32,
+    },
+    /// Narrow notch in the passband (botched EQ / band-reject filtering)
+    SpectralHole {
+        frequency_hz: u32,
+        depth_db: f64,
+        width_hz: u32,
+    },
 }
 
 impl DefectType {
@@ -196,6 +244,19 @@ impl DefectType {
             _ => None,
         }
     }
+
+    /// Lowpass cutoff for any transcode variant
+    pub fn cutoff_hz(&self) -> Option<u32> {
+        match self {
+            DefectType::Mp3Transcode { cutoff_hz, .. }
+            | DefectType::AacTranscode { cutoff_hz, .. }
+            | DefectType::OpusTranscode { cutoff_hz, .. }
+            | DefectType::OggVorbisTranscode { cutoff_hz, .. }
+            | DefectType::LossyTranscode { cutoff_hz, .. }
+            | DefectType::UpsampledLossyTranscode { cutoff_hz, .. } => Some(*cutoff_hz),
+            _ => None,
+        }
+    }
 }
 
 /// Severity levels for detections
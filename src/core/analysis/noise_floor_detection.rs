@@ -0,0 +1,261 @@
+//! Noise floor / gating detection module
+//!
+//! Real recordings carry an analogue or dither noise floor that never
+//! drops below what the bit depth can represent. Noise reduction and
+//! hard gates leave two signatures this module looks for:
+//!
+//! - **Implausibly clean frames** — passages inside the programme whose
+//!   level sits below the quantisation noise floor of the container
+//! - **Hard gating** — a drop of tens of dB from one frame to the next
+//!   straight onto the programme's quietest level, i.e. the floor is
+//!   switched off rather than decaying. The gated floor does not have to
+//!   be below the bit-depth floor; NR often leaves residual noise
+//!
+//! Leading and trailing silence is ignored so that padding and fades
+//! at the file edges are not reported.
+
+use crate::core::analysis::{DefectType, Detection, DetectionMethod, Severity};
+
+/// Noise floor / gating analyzer
+pub struct NoiseFloorDetector {
+    /// Frame length in samples for per-frame RMS measurement
+    frame_size: usize,
+    /// Margin above the bit-depth floor still treated as "too clean" (dB)
+    clean_margin_db: f64,
+    /// Minimum frame-to-frame drop that counts as a gate closing (dB)
+    gate_drop_db: f64,
+    /// Frames within this of the measured floor count as gated (dB)
+    quiet_margin_db: f64,
+    /// Minimum number of gate closings to report
+    min_gated_regions: usize,
+    /// Minimum fraction of interior frames that must be too clean to report
+    min_clean_ratio: f64,
+}
+
+impl Default for NoiseFloorDetector {
+    fn default() -> Self {
+        Self {
+            frame_size: 2048,
+            clean_margin_db: 3.0,
+            gate_drop_db: 40.0,
+            quiet_margin_db: 6.0,
+            min_gated_regions: 3,
+            min_clean_ratio: 0.05,
+        }
+    }
+}
+
+impl NoiseFloorDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_frame_size(mut self, frame_size: usize) -> Self {
+        self.frame_size = frame_size.max(64);
+        self
+    }
+
+    pub fn with_gate_drop(mut self, drop_db: f64) -> Self {
+        self.gate_drop_db = drop_db;
+        self
+    }
+
+    pub fn with_min_gated_regions(mut self, min: usize) -> Self {
+        self.min_gated_regions = min;
+        self
+    }
+
+    pub fn with_min_clean_ratio(mut self, ratio: f64) -> Self {
+        self.min_clean_ratio = ratio;
+        self
+    }
+
+    /// RMS level of quantisation noise for a given bit depth (dBFS).
+    ///
+    /// One LSB is `2 / 2^bits` of the full [-1, 1] range and uniform
+    /// quantisation noise has an RMS of `LSB / sqrt(12)`.
+    pub fn bit_depth_floor_db(bit_depth: u16) -> f64 {
+        let bits = bit_depth.clamp(8, 32) as f64;
+        -6.0206 * (bits - 1.0) - 10.7918
+    }
+
+    /// Analyze mono samples for gated or implausibly clean noise floors
+    pub fn analyze(&self, samples: &[f32], sample_rate: u32, bit_depth: u16) -> Option<Detection> {
+        let frame_db: Vec<f64> = samples
+            .chunks_exact(self.frame_size)
+            .map(|frame| {
+                let ms =
+                    frame.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / frame.len() as f64;
+                if ms > 1e-20 {
+                    10.0 * ms.log10()
+                } else {
+                    -200.0
+                }
+            })
+            .collect();
+
+        if frame_db.len() < 16 {
+            return None;
+        }
+
+        let floor_db = Self::bit_depth_floor_db(bit_depth);
+        let clean_threshold = floor_db + self.clean_margin_db;
+        let is_clean = |db: f64| db < clean_threshold;
+
+        // Only the programme between the first and last audible frame
+        // counts — edge silence is padding, not processing.
+        let first = frame_db.iter().position(|&db| !is_clean(db))?;
+        let last = frame_db.iter().rposition(|&db| !is_clean(db))?;
+        if last <= first + 2 {
+            return None;
+        }
+        let interior = &frame_db[first..=last];
+
+        let clean_frames = interior.iter().filter(|&&db| is_clean(db)).count();
+        let clean_ratio = clean_frames as f64 / interior.len() as f64;
+
+        // Measured floor: 10th percentile of interior frame levels
+        let mut sorted = interior.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let noise_floor_db = sorted[sorted.len() / 10];
+
+        // A gate closing is a sharp drop landing on the measured floor
+        let is_quiet = |db: f64| db <= noise_floor_db + self.quiet_margin_db;
+        let gated_regions = interior
+            .windows(2)
+            .filter(|w| w[0] - w[1] >= self.gate_drop_db && is_quiet(w[1]))
+            .count();
+
+        if gated_regions < self.min_gated_regions && clean_ratio < self.min_clean_ratio {
+            return None;
+        }
+
+        let gate_score = gated_regions as f64 / (self.min_gated_regions.max(1) * 2) as f64;
+        let clean_score = clean_ratio / (self.min_clean_ratio.max(1e-6) * 4.0);
+        let confidence = gate_score.max(clean_score).clamp(0.5, 0.95);
+
+        let frame_secs = self.frame_size as f64 / sample_rate as f64;
+
+        Some(Detection {
+            defect_type: DefectType::NoiseReduction {
+                noise_floor_db,
+                bit_depth_floor_db: floor_db,
+                gated_regions: gated_regions as u32,
+            },
+            confidence,
+            severity: Severity::Low,
+            method: DetectionMethod::NoiseFloorAnalysis,
+            evidence: Some(format!(
+                "{} gate closings, {:.1}% of programme ({:.1}s) below the {}-bit floor of {:.1} dBFS",
+                gated_regions,
+                clean_ratio * 100.0,
+                clean_frames as f64 * frame_secs,
+                bit_depth,
+                floor_db
+            )),
+            temporal: None,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Deterministic uniform noise in [-0.5, 0.5), from a 32-bit LCG.
+    /// Shared with the detector tests.
+    pub(crate) fn white_noise(n: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                state as f32 / u32::MAX as f32 - 0.5
+            })
+            .collect()
+    }
+
+    /// Tone plus a -70 dBFS pseudo-random noise floor
+    fn noisy_tone(n: usize, sample_rate: u32) -> Vec<f32> {
+        white_noise(n, 0x1234_5678)
+            .into_iter()
+            .enumerate()
+            .map(|(i, noise)| {
+                let t = i as f32 / sample_rate as f32;
+                0.3 * (2.0 * std::f32::consts::PI * 440.0 * t).sin() + noise * 0.001
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_natural_noise_floor_not_flagged() {
+        let samples = noisy_tone(44100 * 4, 44100);
+        let result = NoiseFloorDetector::new().analyze(&samples, 44100, 16);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_hard_gated_noise_floor_detected() {
+        // Alternate 0.5 s of programme with 0.5 s of digital black
+        let sr = 44100;
+        let mut samples = noisy_tone(sr as usize * 4, sr);
+        for (i, s) in samples.iter_mut().enumerate() {
+            if (i / (sr as usize / 2)) % 2 == 1 {
+                *s = 0.0;
+            }
+        }
+
+        let result = NoiseFloorDetector::new().analyze(&samples, sr, 16);
+        let detection = result.expect("Hard-gated noise floor should be detected");
+
+        assert_eq!(detection.method, DetectionMethod::NoiseFloorAnalysis);
+        if let DefectType::NoiseReduction { gated_regions, .. } = detection.defect_type {
+            assert!(
+                gated_regions >= 3,
+                "Expected >= 3 gate closings, got {}",
+                gated_regions
+            );
+        } else {
+            panic!("Expected NoiseReduction defect type");
+        }
+    }
+
+    #[test]
+    fn test_gate_to_residual_noise_detected() {
+        // Gate closes onto ~-85 dBFS residual noise, which is well above
+        // the 16-bit floor, so no frame is "implausibly clean"
+        let sr = 44100;
+        let mut samples = noisy_tone(sr as usize * 4, sr);
+        let residual = white_noise(samples.len(), 0x9E37_79B9);
+        for (i, s) in samples.iter_mut().enumerate() {
+            if (i / (sr as usize / 2)) % 2 == 1 {
+                *s = residual[i] * 2e-4;
+            }
+        }
+
+        let detection = NoiseFloorDetector::new()
+            .analyze(&samples, sr, 16)
+            .expect("Gating to residual noise should be detected");
+
+        if let DefectType::NoiseReduction {
+            noise_floor_db,
+            bit_depth_floor_db,
+            gated_regions,
+        } = detection.defect_type
+        {
+            assert!(noise_floor_db > bit_depth_floor_db + 10.0);
+            assert!(
+                gated_regions >= 3,
+                "Expected >= 3 gate closings, got {}",
+                gated_regions
+            );
+        } else {
+            panic!("Expected NoiseReduction defect type");
+        }
+    }
+
+    #[test]
+    fn test_bit_depth_floor() {
+        assert!((NoiseFloorDetector::bit_depth_floor_db(16) + 101.1).abs() < 0.1);
+        assert!((NoiseFloorDetector::bit_depth_floor_db(24) + 149.3).abs() < 0.1);
+    }
+}
@@ -0,0 +1,286 @@
+//! Null-test comparison module
+//!
+//! Subtracts a candidate file from a reference file and measures the
+//! residual. A plain subtraction reports trivially transformed copies as
+//! maximally different, so each channel is corrected before nulling for:
+//!
+//! - **Polarity inversion** — a channel whose correlation with the
+//!   reference is negative is flipped before subtraction
+//! - **DC offset** — the mean of each channel is removed from both sides
+//! - **Clipping** — samples at full scale in either file are excluded,
+//!   since a DC shift pushes peaks into the clip limit and leaves a
+//!   residual that says nothing about the content
+
+use serde::{Deserialize, Serialize};
+
+/// Per-channel null-test result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelNullResult {
+    /// Candidate channel is polarity-inverted relative to the reference
+    pub polarity_inverted: bool,
+    /// DC offset of the candidate relative to the reference (linear)
+    pub dc_offset: f64,
+    /// Residual of a plain subtraction, relative to reference energy (dB)
+    pub raw_residual_db: f64,
+    /// Residual after polarity / DC correction, relative to reference energy (dB)
+    pub corrected_residual_db: f64,
+    /// Number of samples skipped because either side was clipped
+    pub clipped_samples: usize,
+}
+
+/// Complete null-test result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NullCompareResult {
+    pub channels: Vec<ChannelNullResult>,
+    /// Worst corrected residual across channels (dB)
+    pub residual_db: f64,
+    /// Files null out once polarity / DC offset are accounted for
+    pub effectively_identical: bool,
+    /// Any channel required polarity or DC correction to null
+    pub trivially_transformed: bool,
+    /// Number of frames compared (shorter of the two inputs)
+    pub compared_frames: usize,
+}
+
+/// Null-test comparer
+pub struct NullComparer {
+    /// Corrected residual below which files count as identical (dB)
+    identical_threshold_db: f64,
+    /// Absolute sample level treated as clipped
+    clip_threshold: f64,
+    /// DC offset above which a channel counts as shifted
+    dc_tolerance: f64,
+}
+
+impl Default for NullComparer {
+    fn default() -> Self {
+        Self {
+            identical_threshold_db: -60.0,
+            clip_threshold: 0.999,
+            dc_tolerance: 1e-4,
+        }
+    }
+}
+
+impl NullComparer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_identical_threshold(mut self, threshold_db: f64) -> Self {
+        self.identical_threshold_db = threshold_db;
+        self
+    }
+
+    pub fn with_clip_threshold(mut self, threshold: f64) -> Self {
+        self.clip_threshold = threshold;
+        self
+    }
+
+    /// Null `candidate` against `reference`.
+    ///
+    /// Both inputs are interleaved with the same channel count. If the
+    /// lengths differ only the common prefix is compared.
+    pub fn compare(
+        &self,
+        reference: &[f32],
+        candidate: &[f32],
+        channels: u16,
+    ) -> NullCompareResult {
+        let n_channels = channels.max(1) as usize;
+        let frames = reference.len().min(candidate.len()) / n_channels;
+
+        let channel_results: Vec<ChannelNullResult> = (0..n_channels)
+            .map(|ch| {
+                let r: Vec<f64> = (0..frames)
+                    .map(|i| reference[i * n_channels + ch] as f64)
+                    .collect();
+                let c: Vec<f64> = (0..frames)
+                    .map(|i| candidate[i * n_channels + ch] as f64)
+                    .collect();
+                self.compare_channel(&r, &c)
+            })
+            .collect();
+
+        let residual_db = channel_results
+            .iter()
+            .map(|c| c.corrected_residual_db)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let residual_db = if residual_db.is_finite() {
+            residual_db
+        } else {
+            -200.0
+        };
+
+        let trivially_transformed = channel_results
+            .iter()
+            .any(|c| c.polarity_inverted || c.dc_offset.abs() > self.dc_tolerance);
+
+        NullCompareResult {
+            channels: channel_results,
+            residual_db,
+            effectively_identical: frames > 0 && residual_db < self.identical_threshold_db,
+            trivially_transformed,
+            compared_frames: frames,
+        }
+    }
+
+    fn compare_channel(&self, reference: &[f64], candidate: &[f64]) -> ChannelNullResult {
+        // Only unclipped samples take part in offset / polarity estimation
+        // and in the corrected residual.
+        let usable: Vec<bool> = reference
+            .iter()
+            .zip(candidate)
+            .map(|(&r, &c)| r.abs() < self.clip_threshold && c.abs() < self.clip_threshold)
+            .collect();
+        let n_usable = usable.iter().filter(|&&u| u).count();
+        let clipped_samples = reference.len() - n_usable;
+
+        let raw_residual_db = residual_db(reference.iter().zip(candidate).map(|(&r, &c)| (r, c)));
+
+        if n_usable == 0 {
+            return ChannelNullResult {
+                polarity_inverted: false,
+                dc_offset: 0.0,
+                raw_residual_db,
+                corrected_residual_db: raw_residual_db,
+                clipped_samples,
+            };
+        }
+
+        let pairs = || {
+            reference
+                .iter()
+                .zip(candidate)
+                .zip(&usable)
+                .filter(|&(_, &u)| u)
+                .map(|((&r, &c), _)| (r, c))
+        };
+
+        let mean_r = pairs().map(|(r, _)| r).sum::<f64>() / n_usable as f64;
+        let mean_c = pairs().map(|(_, c)| c).sum::<f64>() / n_usable as f64;
+
+        let correlation: f64 = pairs().map(|(r, c)| (r - mean_r) * (c - mean_c)).sum();
+        let polarity_inverted = correlation < 0.0;
+        let sign = if polarity_inverted { -1.0 } else { 1.0 };
+
+        // The offset is expressed in the reference's polarity so that
+        // `candidate == -(reference + dc)` and `candidate == reference + dc`
+        // both report `dc`.
+        let dc_offset = sign * mean_c - mean_r;
+
+        let corrected_residual_db =
+            residual_db(pairs().map(|(r, c)| (r - mean_r, sign * (c - mean_c))));
+
+        ChannelNullResult {
+            polarity_inverted,
+            dc_offset,
+            raw_residual_db,
+            corrected_residual_db,
+            clipped_samples,
+        }
+    }
+}
+
+/// Residual energy relative to reference energy, in dB.
+fn residual_db(pairs: impl Iterator<Item = (f64, f64)>) -> f64 {
+    let mut ref_energy = 0.0_f64;
+    let mut res_energy = 0.0_f64;
+    for (r, c) in pairs {
+        ref_energy += r * r;
+        res_energy += (c - r) * (c - r);
+    }
+
+    if res_energy <= 1e-20 {
+        -200.0
+    } else if ref_energy <= 1e-20 {
+        0.0
+    } else {
+        10.0 * (res_energy / ref_energy).log10()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_signal(n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / 44100.0;
+                0.5 * (2.0 * std::f32::consts::PI * 440.0 * t).sin()
+                    + 0.2 * (2.0 * std::f32::consts::PI * 3_150.0 * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_polarity_inverted_copy_nulls() {
+        let reference = test_signal(44100);
+        let inverted: Vec<f32> = reference.iter().map(|&s| -s).collect();
+
+        let result = NullComparer::new().compare(&reference, &inverted, 1);
+        let ch = &result.channels[0];
+
+        assert!(ch.polarity_inverted);
+        assert!(
+            ch.raw_residual_db > 0.0,
+            "Plain subtraction should be maximally different, got {:.1} dB",
+            ch.raw_residual_db
+        );
+        assert!(
+            result.residual_db < -100.0,
+            "Residual after inversion correction should be near zero, got {:.1} dB",
+            result.residual_db
+        );
+        assert!(result.effectively_identical);
+        assert!(result.trivially_transformed);
+    }
+
+    #[test]
+    fn test_dc_offset_copy_nulls() {
+        let reference = test_signal(44100);
+        let shifted: Vec<f32> = reference.iter().map(|&s| s + 0.05).collect();
+
+        let result = NullComparer::new().compare(&reference, &shifted, 1);
+
+        assert!(!result.channels[0].polarity_inverted);
+        assert!((result.channels[0].dc_offset - 0.05).abs() < 1e-3);
+        assert!(result.effectively_identical);
+    }
+
+    #[test]
+    fn test_clipped_inverted_copy_nulls() {
+        // +0.4 DC pushes the 0.7 peaks past full scale; the inverted copy is
+        // then hard-clipped at ±1.0
+        let reference = test_signal(44100);
+        let clipped: Vec<f32> = reference
+            .iter()
+            .map(|&s| -(s + 0.4).clamp(-1.0, 1.0))
+            .collect();
+
+        let result = NullComparer::new().compare(&reference, &clipped, 1);
+        let ch = &result.channels[0];
+
+        assert!(ch.clipped_samples > 0);
+        assert!(ch.polarity_inverted);
+        assert!((ch.dc_offset - 0.4).abs() < 1e-3);
+        assert!(ch.raw_residual_db > 0.0);
+        assert!(
+            result.effectively_identical,
+            "Clipped samples should be excluded, got {:.1} dB",
+            result.residual_db
+        );
+    }
+
+    #[test]
+    fn test_different_content_does_not_null() {
+        let reference = test_signal(44100);
+        let other: Vec<f32> = (0..44100)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 1_000.0 * i as f32 / 44100.0).sin())
+            .collect();
+
+        let result = NullComparer::new().compare(&reference, &other, 1);
+        assert!(!result.effectively_identical);
+    }
+}
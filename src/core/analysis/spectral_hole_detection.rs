@@ -0,0 +1,291 @@
+//! Spectral hole detection module
+//!
+//! Detects narrow notches in the passband left behind by botched EQ or
+//! band-reject filtering. A hole is a short run of bins that sits well
+//! below the spectrum on *both* sides of it — which is what separates it
+//! from a lowpass / brickwall cutoff, where the spectrum never recovers.
+//!
+//! Works on the averaged power spectrum produced by
+//! `SpectralAnalyzer::compute_power_spectrum_db`.
+
+use crate::core::analysis::{DefectType, Detection, DetectionMethod, Severity};
+
+/// A single notch found in the spectrum
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpectralHole {
+    /// Frequency of the deepest bin in the hole (Hz)
+    pub frequency_hz: f64,
+    /// Depth below the shallower of the two flanks (dB)
+    pub depth_db: f64,
+    /// Width of the region below the depth threshold (Hz)
+    pub width_hz: f64,
+}
+
+/// Spectral hole analyzer
+pub struct SpectralHoleDetector {
+    /// Half-width of the power-domain smoothing applied first (Hz)
+    smooth_hz: f64,
+    /// Minimum depth below both flanks to count as a hole (dB)
+    min_depth_db: f64,
+    /// Widest region still treated as a "narrow" hole (Hz)
+    max_width_hz: f64,
+    /// Width of each flank used as the local reference (Hz)
+    flank_hz: f64,
+    /// Flanks quieter than this are noise, not passband (dB)
+    min_flank_db: f64,
+    /// Lowest frequency searched (Hz)
+    min_freq_hz: f64,
+    /// More holes than this means a sparse / tonal spectrum, not a notch
+    max_holes: usize,
+}
+
+impl Default for SpectralHoleDetector {
+    fn default() -> Self {
+        Self {
+            smooth_hz: 50.0,
+            min_depth_db: 20.0,
+            max_width_hz: 1_000.0,
+            flank_hz: 1_000.0,
+            min_flank_db: -90.0,
+            min_freq_hz: 200.0,
+            max_holes: 3,
+        }
+    }
+}
+
+impl SpectralHoleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_min_depth(mut self, depth_db: f64) -> Self {
+        self.min_depth_db = depth_db;
+        self
+    }
+
+    pub fn with_max_width(mut self, width_hz: f64) -> Self {
+        self.max_width_hz = width_hz;
+        self
+    }
+
+    /// Find narrow holes in `spectrum_db` (bins 0..=Nyquist).
+    ///
+    /// `upper_hz` limits the search to the passband — pass the detected
+    /// lossy cutoff when there is one so the brickwall itself is never
+    /// examined.
+    pub fn find_holes(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        upper_hz: Option<f64>,
+    ) -> Vec<SpectralHole> {
+        if spectrum_db.len() < 64 {
+            return Vec::new();
+        }
+
+        let nyquist = sample_rate as f64 / 2.0;
+        let bin_hz = nyquist / (spectrum_db.len() - 1) as f64;
+
+        let smoothed = self.smooth(spectrum_db, bin_hz);
+
+        let gap = (self.max_width_hz / 2.0 / bin_hz).ceil() as usize;
+        let flank = (self.flank_hz / bin_hz).ceil().max(4.0) as usize;
+
+        let upper = upper_hz.unwrap_or(nyquist).min(nyquist * 0.9);
+        let lo_bin = ((self.min_freq_hz / bin_hz).ceil() as usize).max(gap + flank);
+        let hi_bin =
+            ((upper / bin_hz).floor() as usize).min(smoothed.len().saturating_sub(gap + flank + 1));
+        if hi_bin <= lo_bin {
+            return Vec::new();
+        }
+
+        // Per-bin reference: the quieter of the two flank medians
+        let reference: Vec<f64> = (lo_bin..=hi_bin)
+            .map(|i| {
+                let left = median(&smoothed[i - gap - flank..i - gap]);
+                let right = median(&smoothed[i + gap + 1..=i + gap + flank]);
+                left.min(right)
+            })
+            .collect();
+
+        let mut holes = Vec::new();
+        let mut run_start: Option<usize> = None;
+
+        for (k, i) in (lo_bin..=hi_bin).enumerate() {
+            let deep =
+                reference[k] > self.min_flank_db && smoothed[i] < reference[k] - self.min_depth_db;
+
+            match (deep, run_start) {
+                (true, None) => run_start = Some(k),
+                (false, Some(start)) => {
+                    if let Some(hole) =
+                        self.make_hole(&smoothed, &reference, lo_bin, start, k, bin_hz)
+                    {
+                        holes.push(hole);
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+        // A run still open at the top of the range never recovered —
+        // that's a cutoff edge, not a hole.
+
+        holes
+    }
+
+    /// Run hole detection and convert the result to a `Detection`
+    pub fn detect(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        upper_hz: Option<f64>,
+    ) -> Option<Detection> {
+        let holes = self.find_holes(spectrum_db, sample_rate, upper_hz);
+
+        if holes.is_empty() || holes.len() > self.max_holes {
+            return None;
+        }
+
+        let deepest = holes.iter().max_by(|a, b| {
+            a.depth_db
+                .partial_cmp(&b.depth_db)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+
+        let confidence = (0.5 + (deepest.depth_db - self.min_depth_db) / 40.0).clamp(0.5, 0.9);
+
+        let listing: Vec<String> = holes
+            .iter()
+            .map(|h| {
+                format!(
+                    "{:.0} Hz (−{:.1} dB, {:.0} Hz wide)",
+                    h.frequency_hz, h.depth_db, h.width_hz
+                )
+            })
+            .collect();
+
+        Some(Detection {
+            defect_type: DefectType::SpectralHole {
+                frequency_hz: deepest.frequency_hz as u32,
+                depth_db: deepest.depth_db,
+                width_hz: deepest.width_hz as u32,
+            },
+            confidence,
+            severity: Severity::Low,
+            method: DetectionMethod::SpectralShape,
+            evidence: Some(format!("Narrow passband notch at {}", listing.join(", "))),
+            temporal: None,
+        })
+    }
+
+    fn make_hole(
+        &self,
+        smoothed: &[f64],
+        reference: &[f64],
+        lo_bin: usize,
+        start: usize,
+        end: usize,
+        bin_hz: f64,
+    ) -> Option<SpectralHole> {
+        let width_hz = (end - start) as f64 * bin_hz;
+        if width_hz > self.max_width_hz {
+            return None;
+        }
+
+        let (k_min, &db_min) = smoothed[lo_bin + start..lo_bin + end]
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))?;
+        let k = start + k_min;
+
+        Some(SpectralHole {
+            frequency_hz: (lo_bin + k) as f64 * bin_hz,
+            depth_db: reference[k] - db_min,
+            width_hz,
+        })
+    }
+
+    /// Moving average in the power domain, returned in dB.
+    fn smooth(&self, spectrum_db: &[f64], bin_hz: f64) -> Vec<f64> {
+        let half = (self.smooth_hz / bin_hz).round() as usize;
+        let power: Vec<f64> = spectrum_db
+            .iter()
+            .map(|&db| 10f64.powf(db / 10.0))
+            .collect();
+
+        (0..power.len())
+            .map(|i| {
+                let lo = i.saturating_sub(half);
+                let hi = (i + half + 1).min(power.len());
+                let mean = power[lo..hi].iter().sum::<f64>() / (hi - lo) as f64;
+                if mean > 1e-20 {
+                    10.0 * mean.log10()
+                } else {
+                    -200.0
+                }
+            })
+            .collect()
+    }
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted[sorted.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::analysis::encoder_fingerprint::tests::{synthetic_spectrum, BINS, SR};
+
+    /// Gently sloping spectrum, with an optional 300 Hz notch and a
+    /// brickwall lowpass
+    fn notched_spectrum(notch_hz: Option<f64>, cutoff_hz: Option<f64>) -> Vec<f64> {
+        let bin_hz = SR as f64 / 2.0 / (BINS - 1) as f64;
+        let mut spectrum = synthetic_spectrum(0.0, cutoff_hz.unwrap_or(f64::INFINITY), 1.0);
+        if let Some(n) = notch_hz {
+            for (i, db) in spectrum.iter_mut().enumerate() {
+                if (i as f64 * bin_hz - n).abs() < 150.0 {
+                    *db -= 40.0;
+                }
+            }
+        }
+        spectrum
+    }
+
+    #[test]
+    fn test_notch_at_5khz_detected() {
+        let spectrum = notched_spectrum(Some(5_000.0), None);
+        let detection = SpectralHoleDetector::new()
+            .detect(&spectrum, SR, None)
+            .expect("5 kHz notch should be detected");
+
+        if let DefectType::SpectralHole { frequency_hz, .. } = detection.defect_type {
+            assert!(
+                (4_850..=5_150).contains(&frequency_hz),
+                "Hole should be near 5 kHz, got {} Hz",
+                frequency_hz
+            );
+        } else {
+            panic!("Expected SpectralHole defect type");
+        }
+    }
+
+    #[test]
+    fn test_smooth_spectrum_has_no_holes() {
+        let spectrum = notched_spectrum(None, None);
+        assert!(SpectralHoleDetector::new()
+            .detect(&spectrum, SR, None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_brickwall_cutoff_is_not_a_hole() {
+        let spectrum = notched_spectrum(None, Some(16_000.0));
+        assert!(SpectralHoleDetector::new()
+            .find_holes(&spectrum, SR, None)
+            .is_empty());
+    }
+}
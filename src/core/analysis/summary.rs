@@ -0,0 +1,747 @@
+//! Text summary rendering for a single analysis result
+//!
+//! Produces the same per-file block the CLI prints as a `String`, so that
+//! GUIs and servers can reuse the formatting without going through
+//! `OutputHandler` and its stdout/stderr coupling. `to_summary_string`
+//! is plain text; the CLI uses `to_summary_string_colored` for terminal
+//! colours.
+
+use crate::core::analysis::{AnalysisResult, DefectType, Severity};
+use colorful::{Color, Colorful};
+use std::fmt::{self, Write};
+
+/// How much detail to include in a text summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Header, badges, and detections for suspect files
+    #[default]
+    Normal,
+    /// Everything, including signal metrics and the full file path
+    Verbose,
+}
+
+impl From<bool> for Verbosity {
+    fn from(verbose: bool) -> Self {
+        if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+impl AnalysisResult {
+    /// Render the human-readable summary block for this file as plain
+    /// text, without terminal colour codes.
+    pub fn to_summary_string(&self, verbosity: Verbosity) -> String {
+        self.render_summary(verbosity, Style { color: false })
+    }
+
+    /// Render the summary block with terminal colour codes, exactly as
+    /// `audiocheckr` prints it per file in text mode.
+    pub fn to_summary_string_colored(&self, verbosity: Verbosity) -> String {
+        self.render_summary(verbosity, Style { color: true })
+    }
+
+    fn render_summary(&self, verbosity: Verbosity, style: Style) -> String {
+        let mut out = String::new();
+        write_summary(self, verbosity, style, &mut out).expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// Terminal styling, or a pass-through for plain output
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    color: bool,
+}
+
+impl Style {
+    fn paint(self, text: &str, color: Color) -> String {
+        if self.color {
+            text.color(color).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn badge(self, text: &str, fg: Color, bg: Color) -> String {
+        if self.color {
+            text.color(fg).bg_color(bg).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn dim(self, text: &str) -> String {
+        self.paint(text, Color::DarkGray)
+    }
+}
+
+// ============================================================================
+// Badges (terminal only)
+// ============================================================================
+
+/// Terminal badge (colors, not serialized)
+struct TermBadge {
+    label: &'static str,
+    fg: Color,
+    bg: Color,
+}
+
+fn defect_term_badge(defect: &DefectType) -> TermBadge {
+    match defect {
+        // ── Codec-specific transcode variants ───────────────────
+        DefectType::Mp3Transcode { .. } => TermBadge {
+            label: " MP3 ",
+            fg: Color::White,
+            bg: Color::Red,
+        },
+        DefectType::AacTranscode { .. } => TermBadge {
+            label: " AAC ",
+            fg: Color::White,
+            bg: Color::Red,
+        },
+        DefectType::OpusTranscode { .. } => TermBadge {
+            label: " OPUS ",
+            fg: Color::White,
+            bg: Color::Red,
+        },
+        DefectType::OggVorbisTranscode { .. } => TermBadge {
+            label: " VORBIS ",
+            fg: Color::White,
+            bg: Color::Red,
+        },
+        // ── Generic lossy fallback ──────────────────────────────
+        DefectType::LossyTranscode { codec, .. } => match codec.to_uppercase().as_str() {
+            "MP3" => TermBadge {
+                label: " MP3 ",
+                fg: Color::White,
+                bg: Color::Red,
+            },
+            "AAC" => TermBadge {
+                label: " AAC ",
+                fg: Color::White,
+                bg: Color::Red,
+            },
+            "OPUS" => TermBadge {
+                label: " OPUS ",
+                fg: Color::White,
+                bg: Color::Red,
+            },
+            "VORBIS" => TermBadge {
+                label: " VORBIS ",
+                fg: Color::White,
+                bg: Color::Red,
+            },
+            _ => TermBadge {
+                label: " LOSSY ",
+                fg: Color::White,
+                bg: Color::Red,
+            },
+        },
+        DefectType::Upsampled { .. } => TermBadge {
+            label: " UPSAMPLED ",
+            fg: Color::Black,
+            bg: Color::Yellow,
+        },
+        DefectType::BitDepthInflated { .. } => TermBadge {
+            label: " BIT DEPTH ",
+            fg: Color::Black,
+            bg: Color::Yellow,
+        },
+        DefectType::Clipping { .. } => TermBadge {
+            label: " CLIPPING ",
+            fg: Color::White,
+            bg: Color::Magenta,
+        },
+        DefectType::SilencePadding { .. } => TermBadge {
+            label: " PADDING ",
+            fg: Color::White,
+            bg: Color::Blue,
+        },
+        DefectType::MqaEncoded { .. } => TermBadge {
+            label: " MQA ",
+            fg: Color::White,
+            bg: Color::Cyan,
+        },
+        DefectType::UpsampledLossyTranscode { .. } => TermBadge {
+            label: " UPSAMPLED+LOSSY ",
+            fg: Color::White,
+            bg: Color::Red,
+        },
+        DefectType::DitheringDetected { .. } => TermBadge {
+            label: " DITHER ",
+            fg: Color::White,
+            bg: Color::Blue,
+        },
+        DefectType::ResamplingDetected { .. } => TermBadge {
+            label: " RESAMPLED ",
+            fg: Color::Black,
+            bg: Color::Yellow,
+        },
+        DefectType::LoudnessWarVictim { .. } => TermBadge {
+            label: " LOUDNESS WAR ",
+            fg: Color::White,
+            bg: Color::Magenta,
+        },
+        DefectType::NoiseReduction { .. } => TermBadge {
+            label: " NR / GATED ",
+            fg: Color::White,
+            bg: Color::Magenta,
+        },
+        DefectType::SpectralHole { .. } => TermBadge {
+            label: " NOTCH ",
+            fg: Color::White,
+            bg: Color::Magenta,
+        },
+    }
+}
+
+fn severity_term_badge(severity: &Severity) -> (&'static str, Color) {
+    match severity {
+        Severity::Critical => ("CRIT", Color::Red),
+        Severity::High => ("HIGH", Color::Red),
+        Severity::Medium => (" MED", Color::Yellow),
+        Severity::Low => (" LOW", Color::Blue),
+        Severity::Info => ("INFO", Color::Cyan),
+    }
+}
+
+// ============================================================================
+// Quality Bar (terminal only)
+// ============================================================================
+
+fn quality_bar(style: Style, score: f64, width: usize) -> String {
+    let filled = ((score * width as f64).round() as usize).min(width);
+    let empty = width - filled;
+
+    let bar_color = if score >= 0.9 {
+        Color::Green
+    } else if score >= 0.7 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    format!(
+        "{}{} {:.0}%",
+        style.paint(&"█".repeat(filled), bar_color),
+        style.dim(&"░".repeat(empty)),
+        score * 100.0
+    )
+}
+
+pub(crate) fn verdict_label(genuine: bool, score: f64) -> &'static str {
+    if genuine {
+        if score >= 0.9 {
+            "Lossless"
+        } else {
+            "Probably Lossless"
+        }
+    } else if score < 0.5 {
+        "Lossy / Fake"
+    } else {
+        "Suspect"
+    }
+}
+
+fn verdict_display(style: Style, genuine: bool, score: f64) -> String {
+    let label = verdict_label(genuine, score);
+    if genuine {
+        style.paint(label, Color::Green)
+    } else if score < 0.5 {
+        style.paint(label, Color::Red)
+    } else {
+        style.paint(label, Color::Yellow)
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+pub(crate) fn format_sample_rate(rate: u32) -> String {
+    if rate % 1000 == 0 {
+        format!("{} kHz", rate / 1000)
+    } else {
+        format!("{:.1} kHz", rate as f64 / 1000.0)
+    }
+}
+
+/// Format seconds as `m:ss`
+fn format_timestamp(secs: f64) -> String {
+    let total = secs.round() as u64;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+pub(crate) fn compute_quality_score(result: &AnalysisResult) -> f64 {
+    result
+        .quality_metrics
+        .as_ref()
+        .map(|m| 1.0 - (m.noise_floor.abs() / 120.0).min(1.0))
+        .unwrap_or(if result.is_genuine() { 0.95 } else { 0.4 })
+}
+
+// ============================================================================
+// Defect Detail Formatting
+// ============================================================================
+
+/// Helper: format bitrate + cutoff for any transcode variant
+fn format_transcode_detail(codec: &str, estimated_bitrate: &Option<u32>, cutoff_hz: u32) -> String {
+    let bitrate = estimated_bitrate
+        .map(|b| format!(" @ ~{} kbps", b))
+        .unwrap_or_default();
+    format!("{}{}, cutoff {} Hz", codec, bitrate, cutoff_hz)
+}
+
+pub(crate) fn format_defect_detail(defect: &DefectType) -> String {
+    match defect {
+        // ── Codec-specific transcode variants ───────────────────
+        DefectType::Mp3Transcode {
+            estimated_bitrate,
+            cutoff_hz,
+        } => format_transcode_detail("MP3", estimated_bitrate, *cutoff_hz),
+        DefectType::AacTranscode {
+            estimated_bitrate,
+            cutoff_hz,
+        } => format_transcode_detail("AAC", estimated_bitrate, *cutoff_hz),
+        DefectType::OpusTranscode {
+            estimated_bitrate,
+            cutoff_hz,
+        } => format_transcode_detail("Opus", estimated_bitrate, *cutoff_hz),
+        DefectType::OggVorbisTranscode {
+            estimated_bitrate,
+            cutoff_hz,
+        } => format_transcode_detail("OggVorbis", estimated_bitrate, *cutoff_hz),
+        // ── Generic lossy fallback ──────────────────────────────
+        DefectType::LossyTranscode {
+            codec,
+            estimated_bitrate,
+            cutoff_hz,
+        } => format_transcode_detail(codec, estimated_bitrate, *cutoff_hz),
+        DefectType::Upsampled {
+            original_rate,
+            current_rate,
+        } => {
+            format!(
+                "{} → {}",
+                format_sample_rate(*original_rate),
+                format_sample_rate(*current_rate)
+            )
+        }
+        DefectType::BitDepthInflated {
+            actual_bits,
+            claimed_bits,
+        } => {
+            format!(
+                "{}-bit content in {}-bit container",
+                actual_bits, claimed_bits
+            )
+        }
+        DefectType::Clipping {
+            peak_level,
+            clipped_samples,
+        } => {
+            format!(
+                "peak {:.1} dBFS, {} samples clipped",
+                peak_level, clipped_samples
+            )
+        }
+        DefectType::SilencePadding { padding_duration } => {
+            format!("{:.2}s of silence padding", padding_duration)
+        }
+        DefectType::MqaEncoded {
+            encoder_version,
+            bit_depth,
+            ..
+        } => {
+            format!("encoder v{}, {}-bit container", encoder_version, bit_depth)
+        }
+        DefectType::UpsampledLossyTranscode {
+            codec,
+            original_rate,
+            current_rate,
+            estimated_bitrate,
+            cutoff_hz,
+        } => {
+            let bitrate = estimated_bitrate
+                .map(|b| format!(" ~{} kbps", b))
+                .unwrap_or_default();
+            format!(
+                "{}{} upsampled {} → {} Hz, cutoff {} Hz",
+                codec, bitrate, original_rate, current_rate, cutoff_hz
+            )
+        }
+        DefectType::DitheringDetected {
+            dither_type,
+            bit_depth,
+            noise_shaping,
+        } => {
+            let shaping = if *noise_shaping { ", noise-shaped" } else { "" };
+            format!("{} → {}-bit{}", dither_type, bit_depth, shaping)
+        }
+        DefectType::ResamplingDetected {
+            original_rate,
+            target_rate,
+            quality,
+        } => {
+            let orig = if *original_rate > 0 {
+                format!("{} Hz → ", original_rate)
+            } else {
+                String::new()
+            };
+            format!("{}{} Hz ({})", orig, target_rate, quality)
+        }
+        DefectType::LoudnessWarVictim {
+            tt_dr_score,
+            integrated_lufs,
+            plr_db,
+        } => {
+            format!(
+                "DR {:.0}, {:.1} LUFS, PLR {:.1} dB",
+                tt_dr_score, integrated_lufs, plr_db
+            )
+        }
+        DefectType::NoiseReduction {
+            noise_floor_db,
+            bit_depth_floor_db,
+            gated_regions,
+        } => {
+            format!(
+                "noise floor {:.1} dBFS (bit-depth floor {:.1} dBFS), {} gated regions",
+                noise_floor_db, bit_depth_floor_db, gated_regions
+            )
+        }
+        DefectType::SpectralHole {
+            frequency_hz,
+            depth_db,
+            width_hz,
+        } => {
+            format!(
+                "notch at {} Hz, {:.1} dB deep, {} Hz wide",
+                frequency_hz, depth_db, width_hz
+            )
+        }
+    }
+}
+
+// ============================================================================
+// Summary block
+// ============================================================================
+
+fn write_summary(
+    result: &AnalysisResult,
+    verbosity: Verbosity,
+    style: Style,
+    w: &mut String,
+) -> fmt::Result {
+    let verbose = verbosity == Verbosity::Verbose;
+
+    let filename = result
+        .file_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+
+    let genuine = result.is_genuine();
+    let score = compute_quality_score(result);
+
+    // Header
+    let header_icon = if genuine { "✓" } else { "✗" };
+    let header_color = if genuine { Color::Green } else { Color::Red };
+    writeln!(w)?;
+    writeln!(
+        w,
+        "{}  {}",
+        style.paint(header_icon, header_color),
+        style.paint(&filename, Color::White),
+    )?;
+
+    // Metadata
+    writeln!(
+        w,
+        "   {} {} {} {}  {}  {} ch",
+        style.dim("│"),
+        format_sample_rate(result.sample_rate),
+        style.dim("/"),
+        format!("{}-bit", result.bit_depth),
+        result
+            .file_path
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_uppercase(),
+        result.channels,
+    )?;
+
+    // Quality bar
+    writeln!(
+        w,
+        "   {} Quality  {}   {}",
+        style.dim("│"),
+        quality_bar(style, score, 20),
+        verdict_display(style, genuine, score),
+    )?;
+
+    // Badge row
+    if !result.detections.is_empty() {
+        let badges: Vec<String> = result
+            .detections
+            .iter()
+            .map(|d| {
+                let b = defect_term_badge(&d.defect_type);
+                style.badge(b.label, b.fg, b.bg)
+            })
+            .collect();
+        writeln!(w, "   {} {}", style.dim("│"), badges.join(" "))?;
+    }
+
+    // Detailed detections
+    if !result.detections.is_empty() && (verbose || !genuine) {
+        writeln!(w, "   {}", style.dim("│"))?;
+        writeln!(w, "   {}  {}", style.dim("│"), style.dim("Detections"))?;
+
+        for detection in &result.detections {
+            let (sev_label, sev_color) = severity_term_badge(&detection.severity);
+            let conf = format!("{:.0}%", detection.confidence * 100.0);
+
+            writeln!(
+                w,
+                "   {}  {} {}  {}",
+                style.dim("│"),
+                style.paint(sev_label, sev_color),
+                style.dim(&conf),
+                format_defect_detail(&detection.defect_type),
+            )?;
+
+            if let Some(evidence) = &detection.evidence {
+                writeln!(w, "   {}       {}", style.dim("│"), style.dim(evidence))?;
+            }
+        }
+    }
+
+    // Per-segment verdicts
+    if !result.segments.is_empty() && (verbose || result.segments.iter().any(|s| !s.is_genuine())) {
+        writeln!(w, "   {}", style.dim("│"))?;
+        writeln!(w, "   {}  {}", style.dim("│"), style.dim("Segments"))?;
+
+        for segment in &result.segments {
+            let span = format!(
+                "{}–{}",
+                format_timestamp(segment.start_time),
+                format_timestamp(segment.end_time)
+            );
+            let findings: Vec<String> = segment
+                .detections
+                .iter()
+                .filter(|d| !matches!(d.severity, Severity::Info | Severity::Low))
+                .map(|d| format_defect_detail(&d.defect_type))
+                .collect();
+
+            if findings.is_empty() {
+                writeln!(
+                    w,
+                    "   {}  {}  {}  Clean",
+                    style.dim("│"),
+                    style.paint("✓", Color::Green),
+                    span
+                )?;
+            } else {
+                writeln!(
+                    w,
+                    "   {}  {}  {}  {}",
+                    style.dim("│"),
+                    style.paint("✗", Color::Red),
+                    span,
+                    findings.join("; ")
+                )?;
+            }
+        }
+    }
+
+    // Verbose: quality metrics
+    if verbose {
+        if let Some(metrics) = &result.quality_metrics {
+            writeln!(w, "   {}", style.dim("│"))?;
+            writeln!(w, "   {}  {}", style.dim("│"), style.dim("Signal"))?;
+            writeln!(
+                w,
+                "   {}  Dynamic Range  {:.1} dB",
+                style.dim("│"),
+                metrics.dynamic_range
+            )?;
+            writeln!(
+                w,
+                "   {}  Noise Floor    {:.1} dB",
+                style.dim("│"),
+                metrics.noise_floor
+            )?;
+        }
+    }
+
+    // Dynamic range
+    if let Some(ref dr) = result.dynamic_range {
+        writeln!(w, "   {}", style.dim("│"))?;
+        writeln!(w, "   {}  {}", style.dim("│"), style.dim("Dynamic Range"))?;
+        writeln!(
+            w,
+            "   {}  TT DR         {:.1} dB  {}",
+            style.dim("│"),
+            dr.tt_dr_score,
+            dr_verdict_colored(style, &format!("{}", dr.verdict))
+        )?;
+        writeln!(
+            w,
+            "   {}  LUFS          {:.1}",
+            style.dim("│"),
+            dr.integrated_loudness_lufs
+        )?;
+        writeln!(
+            w,
+            "   {}  Crest Factor  {:.1} dB",
+            style.dim("│"),
+            dr.crest_factor_db
+        )?;
+        writeln!(
+            w,
+            "   {}  PLR           {:.1} dB",
+            style.dim("│"),
+            dr.plr_db
+        )?;
+        writeln!(
+            w,
+            "   {}  True Peak     {:.1} dBFS",
+            style.dim("│"),
+            dr.true_peak_dbfs
+        )?;
+        if dr.loudness_war_victim {
+            writeln!(
+                w,
+                "   {}  {}",
+                style.dim("│"),
+                style.paint("⚠ Loudness war victim", Color::Yellow)
+            )?;
+        }
+    }
+
+    // Footer
+    if verbose {
+        writeln!(w, "   {}", style.dim("│"))?;
+        writeln!(
+            w,
+            "   {}  {}",
+            style.dim("╰"),
+            style.dim(&result.file_path.display().to_string())
+        )?;
+    } else {
+        writeln!(w, "   {}", style.dim("╰"))?;
+    }
+
+    Ok(())
+}
+
+fn dr_verdict_colored(style: Style, verdict: &str) -> String {
+    let lower = verdict.to_lowercase();
+    if lower.contains("excellent") || lower.contains("good") {
+        style.paint(verdict, Color::Green)
+    } else if lower.contains("moderate") || lower.contains("acceptable") {
+        style.paint(verdict, Color::Yellow)
+    } else {
+        style.paint(verdict, Color::Red)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::analysis::{Detection, DetectionMethod, SegmentVerdict};
+    use std::path::PathBuf;
+
+    /// A 128 kbps MP3 transcode verdict; also used by the tag writer tests
+    pub(crate) fn transcoded_result() -> AnalysisResult {
+        AnalysisResult {
+            file_path: PathBuf::from("/music/fake.flac"),
+            file_hash: String::new(),
+            sample_rate: 44100,
+            bit_depth: 16,
+            channels: 2,
+            duration: 180.0,
+            detections: vec![Detection {
+                defect_type: DefectType::Mp3Transcode {
+                    estimated_bitrate: Some(128),
+                    cutoff_hz: 16000,
+                },
+                confidence: 0.9,
+                severity: Severity::High,
+                method: DetectionMethod::SpectralCutoff,
+                evidence: Some("Spectral cutoff at 16000 Hz (72.6% of Nyquist)".to_string()),
+                temporal: None,
+            }],
+            confidence: 0.1,
+            quality_metrics: None,
+            analysis_timestamp: String::new(),
+            dynamic_range: None,
+            mfcc: None,
+            segments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary_contains_codec_and_cutoff() {
+        let summary = transcoded_result().to_summary_string(Verbosity::Normal);
+        assert!(summary.contains("fake.flac"));
+        assert!(summary.contains("MP3"), "summary: {}", summary);
+        assert!(summary.contains("cutoff 16000 Hz"), "summary: {}", summary);
+    }
+
+    #[test]
+    fn test_verbose_summary_includes_path() {
+        let result = transcoded_result();
+        let summary = result.to_summary_string(Verbosity::Verbose);
+        assert!(summary.contains("/music/fake.flac"));
+        assert!(!result
+            .to_summary_string(Verbosity::Normal)
+            .contains("/music/fake.flac"));
+    }
+
+    #[test]
+    fn test_default_summary_has_no_escape_codes() {
+        let result = transcoded_result();
+        for verbosity in [Verbosity::Normal, Verbosity::Verbose] {
+            let plain = result.to_summary_string(verbosity);
+            assert!(!plain.contains("\x1b["), "plain: {:?}", plain);
+            assert!(plain.contains("cutoff 16000 Hz"));
+        }
+        assert!(result
+            .to_summary_string_colored(Verbosity::Normal)
+            .contains("\x1b["));
+    }
+
+    #[test]
+    fn test_summary_lists_flagged_segment() {
+        let mut result = transcoded_result();
+        let flagged = result.detections.clone();
+        result.segments = vec![
+            SegmentVerdict {
+                index: 0,
+                start_time: 0.0,
+                end_time: 90.0,
+                detections: Vec::new(),
+                confidence: 1.0,
+            },
+            SegmentVerdict {
+                index: 1,
+                start_time: 90.0,
+                end_time: 180.0,
+                detections: flagged,
+                confidence: 0.1,
+            },
+        ];
+
+        let summary = result.to_summary_string(Verbosity::Normal);
+        assert!(summary.contains("Segments"), "summary: {}", summary);
+        assert!(summary.contains("0:00–1:30  Clean"), "summary: {}", summary);
+        assert!(summary.contains("1:30–3:00"), "summary: {}", summary);
+    }
+}
@@ -32,9 +32,10 @@
 //!   at common original-Nyquist frequencies.
 
 use crate::core::analysis::dynamic_range::{DynamicRangeAnalyzer, DynamicRangeResult};
+use crate::core::analysis::encoder_fingerprint::EncoderFingerprint;
 use crate::core::analysis::{
     AnalysisConfig, AnalysisResult, DefectType, Detection, DetectionMethod, QualityMetrics,
-    Severity,
+    SegmentVerdict, Severity, TemporalDistribution,
 };
 use crate::core::dsp::{SpectralAnalyzer, WindowFunction};
 use anyhow::{Context, Result};
@@ -67,7 +68,16 @@ impl AudioDetector {
         let duration = samples.len() as f64 / (sample_rate as f64 * channels as f64);
         let file_hash = self.calculate_hash(path)?;
 
-        let detections = self.run_detection_pipeline(&samples, sample_rate, bit_depth, channels)?;
+        let mut detections =
+            self.run_detection_pipeline(&samples, sample_rate, bit_depth, channels)?;
+
+        let segments = match self.config.segment_duration {
+            Some(secs) => {
+                self.analyze_segments(&samples, sample_rate, bit_depth, channels, secs)?
+            }
+            None => Vec::new(),
+        };
+        merge_segment_detections(&mut detections, &segments);
 
         let confidence = self.calculate_confidence(&detections);
         let quality_metrics = self.calculate_quality_metrics(&samples, sample_rate);
@@ -98,9 +108,64 @@ impl AudioDetector {
             analysis_timestamp: chrono::Utc::now().to_rfc3339(),
             dynamic_range,
             mfcc,
+            segments,
         })
     }
 
+    /// Run the detection pipeline on consecutive segments of
+    /// `segment_secs` seconds each.
+    ///
+    /// A trailing remainder shorter than half a segment is folded into the
+    /// last segment. Returns an empty list when the file is not longer than
+    /// a single segment, since the whole-file verdict already covers it.
+    pub fn analyze_segments(
+        &self,
+        samples: &[f32],
+        sample_rate: u32,
+        bit_depth: u16,
+        channels: u16,
+        segment_secs: f64,
+    ) -> Result<Vec<SegmentVerdict>> {
+        let n_channels = channels.max(1) as usize;
+        let total_frames = samples.len() / n_channels;
+        let segment_frames = (segment_secs * sample_rate as f64).round() as usize;
+
+        if segment_frames == 0 || total_frames <= segment_frames {
+            return Ok(Vec::new());
+        }
+
+        let mut bounds: Vec<(usize, usize)> = Vec::new();
+        let mut start = 0;
+        while start < total_frames {
+            let end = (start + segment_frames).min(total_frames);
+            if end - start < segment_frames / 2 {
+                if let Some(last) = bounds.last_mut() {
+                    last.1 = end;
+                    break;
+                }
+            }
+            bounds.push((start, end));
+            start = end;
+        }
+
+        bounds
+            .into_iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                let segment = &samples[start * n_channels..end * n_channels];
+                let detections =
+                    self.run_detection_pipeline(segment, sample_rate, bit_depth, channels)?;
+                Ok(SegmentVerdict {
+                    index,
+                    start_time: start as f64 / sample_rate as f64,
+                    end_time: end as f64 / sample_rate as f64,
+                    confidence: self.calculate_confidence(&detections),
+                    detections,
+                })
+            })
+            .collect()
+    }
+
     // ──────────────────────── helpers: loading / basic analysis ────────────────────────
 
     fn load_audio<P: AsRef<Path>>(&self, path: P) -> Result<(Vec<f32>, u32, u16, u16)> {
@@ -256,19 +321,45 @@ impl AudioDetector {
             detections.push(det);
         }
 
-        // 3) Spectral cutoff (codec‑specific, P0/P1) – skipped if resampled
-        //    FIX: use mono downmix, NOT interleaved stereo
-        let spectral_det = if !has_resampling {
-            self.detect_spectral_cutoff(&mono_f64, sample_rate)?
+        // Averaged power spectrum of the mono downmix, shared by the
+        // spectral cutoff and passband checks below; skipped if neither runs
+        let spectrum_db = if !has_resampling || self.config.enable_spectral_holes {
+            Some(self.averaged_spectrum_db(&mono_f64))
         } else {
             None
         };
 
+        // 3) Spectral cutoff (codec‑specific, P0/P1) – skipped if resampled
+        //    FIX: use mono downmix, NOT interleaved stereo
+        let spectral_det = match &spectrum_db {
+            Some(spectrum) if !has_resampling => self
+                .detect_spectral_cutoff(spectrum, sample_rate)?
+                // 3a) Encoder fingerprint — name the likely encoder in the
+                //     evidence, and let it overrule the cutoff bucket's
+                //     MP3/AAC guess
+                .map(|det| self.apply_encoder_fingerprint(det, spectrum, sample_rate)),
+            _ => None,
+        };
+
         if let Some(det) = spectral_det.clone() {
             has_transcode = true;
             detections.push(det);
         }
 
+        // 3b) Narrow spectral holes in the passband (below any cutoff)
+        if self.config.enable_spectral_holes {
+            let passband_hz = spectral_det
+                .as_ref()
+                .and_then(|d| d.defect_type.cutoff_hz())
+                .map(|hz| hz as f64);
+            if let Some(det) = spectrum_db
+                .as_deref()
+                .and_then(|s| self.detect_spectral_holes(s, sample_rate, passband_hz))
+            {
+                detections.push(det);
+            }
+        }
+
         // ── FIX P3: relaxed bit-depth inflation ─────────────────────
         // 4) Bit‑depth inflation (multi‑heuristic, relaxed 2-of-3 voting)
         if let Some(det) = self.detect_bit_depth_inflation_multi(samples, bit_depth)? {
@@ -307,6 +398,13 @@ impl AudioDetector {
             }
         }
 
+        // 7b) Noise floor gating / noise reduction
+        if self.config.enable_noise_floor {
+            if let Some(det) = self.detect_noise_reduction(&mono_f32, sample_rate, bit_depth) {
+                detections.push(det);
+            }
+        }
+
         // ── FIX P1: tightened MFCC/SFM thresholds ──────────────────
         // 8) MFCC + SFM lossy detection – only if spectral cutoff missed
         let mut mfcc_det: Option<Detection> = None;
@@ -443,20 +541,30 @@ impl AudioDetector {
         }))
     }
 
+    /// Median power spectrum (dB) over frames spread across the file.
+    fn averaged_spectrum_db(&self, samples: &[f64]) -> Vec<f64> {
+        let mut analyzer = SpectralAnalyzer::new(
+            self.config.fft_size,
+            self.config.hop_size,
+            WindowFunction::BlackmanHarris,
+        );
+        analyzer.compute_power_spectrum_db(samples)
+    }
+
     /// Spectral cutoff based lossy detector (P0/P1).
     fn detect_spectral_cutoff(
         &self,
-        samples: &[f64],
+        spectrum_db: &[f64],
         sample_rate: u32,
     ) -> Result<Option<Detection>> {
-        let mut analyzer = SpectralAnalyzer::new(
+        let analyzer = SpectralAnalyzer::new(
             self.config.fft_size,
             self.config.hop_size,
             WindowFunction::BlackmanHarris,
         );
 
         let nyquist = sample_rate as f64 / 2.0;
-        let cutoff = analyzer.detect_cutoff(samples, sample_rate, 10.0);
+        let cutoff = analyzer.detect_cutoff_in_spectrum(spectrum_db, sample_rate);
 
         let cutoff_hz = match cutoff {
             Some(v) => v,
@@ -525,6 +633,82 @@ impl AudioDetector {
         }))
     }
 
+    /// Passband notch detector on the averaged spectrum.
+    fn detect_spectral_holes(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        passband_hz: Option<f64>,
+    ) -> Option<Detection> {
+        use crate::core::analysis::spectral_hole_detection::SpectralHoleDetector;
+
+        SpectralHoleDetector::new().detect(spectrum_db, sample_rate, passband_hz)
+    }
+
+    /// Encoder fingerprint from the averaged spectrum around the cutoff.
+    fn fingerprint_encoder(
+        &self,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+        cutoff_hz: f64,
+    ) -> Option<EncoderFingerprint> {
+        use crate::core::analysis::encoder_fingerprint::EncoderFingerprinter;
+
+        EncoderFingerprinter::new().identify(spectrum_db, sample_rate, cutoff_hz)
+    }
+
+    /// Add the encoder fingerprint to a cutoff detection's evidence.
+    ///
+    /// `estimate_codec` only buckets by cutoff, and LAME's usual 17–19 kHz
+    /// lowpasses land in its AAC range. The subband ripple tells the two
+    /// apart directly, so when the fingerprint names the other codec the
+    /// detection is re-classed and the evidence records the disagreement.
+    fn apply_encoder_fingerprint(
+        &self,
+        mut det: Detection,
+        spectrum_db: &[f64],
+        sample_rate: u32,
+    ) -> Detection {
+        let cutoff_hz = match det.defect_type.cutoff_hz() {
+            Some(hz) => hz,
+            None => return det,
+        };
+        let fp = match self.fingerprint_encoder(spectrum_db, sample_rate, cutoff_hz as f64) {
+            Some(fp) => fp,
+            None => return det,
+        };
+
+        // The bitrate estimate came from the other codec's table, so drop it
+        let reclassed = match (&det.defect_type, fp.encoder.codec_name()) {
+            (DefectType::AacTranscode { .. }, "MP3") => Some(DefectType::Mp3Transcode {
+                estimated_bitrate: None,
+                cutoff_hz,
+            }),
+            (DefectType::Mp3Transcode { .. }, "AAC") => Some(DefectType::AacTranscode {
+                estimated_bitrate: None,
+                cutoff_hz,
+            }),
+            _ => None,
+        };
+
+        let mut note = fp.to_string();
+        if let Some(defect_type) = reclassed {
+            note = format!(
+                "{}; cutoff alone suggested {}, re-classed as {}",
+                note,
+                det.defect_type.codec_name().unwrap_or("unknown"),
+                fp.encoder.codec_name()
+            );
+            det.defect_type = defect_type;
+        }
+
+        det.evidence = Some(match det.evidence.take() {
+            Some(evidence) => format!("{}; {}", evidence, note),
+            None => note,
+        });
+        det
+    }
+
     /// Map cutoff frequency → approximate codec/bitrate bucket.
     fn estimate_codec(&self, cutoff_hz: f64) -> (String, u32) {
         if cutoff_hz < 11_000.0 {
@@ -974,6 +1158,17 @@ impl AudioDetector {
         Ok(det.analyze(samples, sample_rate))
     }
 
+    fn detect_noise_reduction(
+        &self,
+        mono: &[f32],
+        sample_rate: u32,
+        bit_depth: u16,
+    ) -> Option<Detection> {
+        use crate::core::analysis::noise_floor_detection::NoiseFloorDetector;
+        let det = NoiseFloorDetector::new();
+        det.analyze(mono, sample_rate, bit_depth)
+    }
+
     /// ── FIX P1/v3: MFCC‑based generic lossy detector (relaxed) ─────
     ///
     /// Changes from v2 → v3:
@@ -1420,9 +1615,52 @@ impl AudioDetector {
     }
 }
 
+/// Fold findings from flagged segments into the file-level detections.
+///
+/// A defect the whole-file pass missed (e.g. a transcoded section spliced
+/// into a clean file) is added once, tagged with the span of the first
+/// segment it was found in, so the file verdict reflects its worst part.
+fn merge_segment_detections(detections: &mut Vec<Detection>, segments: &[SegmentVerdict]) {
+    for segment in segments.iter().filter(|s| !s.is_genuine()) {
+        for det in &segment.detections {
+            if matches!(det.severity, Severity::Info | Severity::Low) {
+                continue;
+            }
+            // Any transcode finding covers the others: a file-level
+            // LossyTranscode and a segment Mp3Transcode are one defect
+            let kind = std::mem::discriminant(&det.defect_type);
+            let lossy = det.defect_type.is_lossy_transcode();
+            if detections.iter().any(|d| {
+                std::mem::discriminant(&d.defect_type) == kind
+                    || (lossy && d.defect_type.is_lossy_transcode())
+            }) {
+                continue;
+            }
+
+            let mut det = det.clone();
+            det.evidence = Some(format!(
+                "Segment {} ({:.1}–{:.1} s): {}",
+                segment.index + 1,
+                segment.start_time,
+                segment.end_time,
+                det.evidence.as_deref().unwrap_or("flagged")
+            ));
+            det.temporal = Some(TemporalDistribution {
+                start_time: segment.start_time,
+                end_time: segment.end_time,
+                peak_time: segment.start_time,
+                distribution: Vec::new(),
+            });
+            detections.push(det);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::analysis::encoder_fingerprint::tests::{synthetic_spectrum, SR};
+    use crate::core::analysis::noise_floor_detection::tests::white_noise;
 
     #[test]
     fn detector_uses_default_config() {
@@ -1449,4 +1687,156 @@ mod tests {
         assert_eq!(c, "aac");
         assert_eq!(b, 256);
     }
+
+    /// Pink-ish noise, optionally brickwall-lowpassed the way a lossy
+    /// encoder would. `n` must be a power of two.
+    fn noise(n: usize, seed: u32, lowpass_hz: Option<f64>, sample_rate: u32) -> Vec<f32> {
+        use rustfft::{num_complex::Complex, FftPlanner};
+
+        let mut buf: Vec<Complex<f32>> = white_noise(n, seed)
+            .into_iter()
+            .map(|x| Complex::new(x, 0.0))
+            .collect();
+
+        let mut planner = FftPlanner::new();
+        planner.plan_fft_forward(n).process(&mut buf);
+        for (k, bin) in buf.iter_mut().enumerate() {
+            let hz = k.min(n - k) as f64 * sample_rate as f64 / n as f64;
+            let gain = if hz < 20.0 || lowpass_hz.is_some_and(|c| hz > c) {
+                0.0
+            } else {
+                (1_000.0 / hz).sqrt()
+            };
+            *bin = *bin * gain as f32;
+        }
+        planner.plan_fft_inverse(n).process(&mut buf);
+
+        let peak = buf.iter().map(|c| c.re.abs()).fold(0.0f32, f32::max);
+        buf.iter().map(|c| 0.5 * c.re / peak).collect()
+    }
+
+    #[test]
+    fn segment_analysis_flags_only_transcoded_half() {
+        let sr = 44_100;
+        let half = 1 << 18; // ~5.9 s
+        let mut samples = noise(half, 1, None, sr);
+        samples.extend(noise(half, 2, Some(16_000.0), sr));
+
+        // Stationary noise has a flat-looking SFM that the statistical
+        // lossy heuristics read as codec output; let the cutoff decide.
+        let d = AudioDetector::new(AnalysisConfig {
+            enable_mfcc: false,
+            ..AnalysisConfig::default()
+        });
+        let segment_secs = half as f64 / sr as f64;
+        let segments = d
+            .analyze_segments(&samples, sr, 16, 1, segment_secs)
+            .unwrap();
+
+        assert_eq!(segments.len(), 2);
+        assert!(
+            segments[0].is_genuine(),
+            "Clean first half should not flag: {:?}",
+            segments[0].detections
+        );
+        assert!(!segments[1].is_genuine());
+        assert!(segments[1]
+            .detections
+            .iter()
+            .any(|d| d.defect_type.is_lossy_transcode()));
+
+        // The file verdict picks up the transcoded segment
+        let mut file_detections = Vec::new();
+        merge_segment_detections(&mut file_detections, &segments);
+        let merged = file_detections
+            .iter()
+            .find(|d| d.defect_type.is_lossy_transcode())
+            .expect("Transcoded segment should reach the file verdict");
+        assert_eq!(
+            merged.temporal.as_ref().map(|t| t.end_time > t.start_time),
+            Some(true)
+        );
+
+        // A generic file-level transcode finding is not doubled up by the
+        // segment's codec-specific one
+        let mut file_detections = vec![Detection {
+            defect_type: DefectType::LossyTranscode {
+                codec: "Unknown".to_string(),
+                estimated_bitrate: None,
+                cutoff_hz: 16_000,
+            },
+            confidence: 0.6,
+            severity: Severity::High,
+            method: DetectionMethod::SpectralCutoff,
+            evidence: None,
+            temporal: None,
+        }];
+        merge_segment_detections(&mut file_detections, &segments);
+        assert_eq!(
+            file_detections
+                .iter()
+                .filter(|d| d.defect_type.is_lossy_transcode())
+                .count(),
+            1
+        );
+    }
+
+    fn cutoff_detection(defect_type: DefectType) -> Detection {
+        Detection {
+            defect_type,
+            confidence: 0.6,
+            severity: Severity::High,
+            method: DetectionMethod::SpectralCutoff,
+            evidence: Some("Spectral cutoff at 17000 Hz".to_string()),
+            temporal: None,
+        }
+    }
+
+    #[test]
+    fn fingerprint_reclasses_lame_cutoff_in_aac_range() {
+        let sr = SR;
+        let d = AudioDetector::with_default_config();
+        let spectrum = synthetic_spectrum(2.0, 17_000.0, 1_000.0);
+
+        // A LAME-style 17 kHz lowpass falls in the AAC cutoff bucket; the
+        // subband ripple overrules it
+        let det = d.apply_encoder_fingerprint(
+            cutoff_detection(DefectType::AacTranscode {
+                estimated_bitrate: Some(256),
+                cutoff_hz: 17_000,
+            }),
+            &spectrum,
+            sr,
+        );
+        assert_eq!(
+            det.defect_type,
+            DefectType::Mp3Transcode {
+                estimated_bitrate: None,
+                cutoff_hz: 17_000,
+            }
+        );
+        let evidence = det.evidence.unwrap();
+        assert!(evidence.contains("LAME"), "{}", evidence);
+        assert!(evidence.contains("suggested AAC"), "{}", evidence);
+
+        // When the bucket already agrees, only the encoder is added
+        let det = d.apply_encoder_fingerprint(
+            cutoff_detection(DefectType::Mp3Transcode {
+                estimated_bitrate: Some(192),
+                cutoff_hz: 17_000,
+            }),
+            &spectrum,
+            sr,
+        );
+        assert!(matches!(
+            det.defect_type,
+            DefectType::Mp3Transcode {
+                estimated_bitrate: Some(192),
+                ..
+            }
+        ));
+        let evidence = det.evidence.unwrap();
+        assert!(evidence.contains("LAME"), "{}", evidence);
+        assert!(!evidence.contains("re-classed"), "{}", evidence);
+    }
 }
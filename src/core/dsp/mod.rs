@@ -243,6 +243,12 @@ impl SpectralAnalyzer {
         _threshold_db: f64, // kept for API compat; internally we use adaptive logic
     ) -> Option<f64> {
         let spectrum = self.compute_power_spectrum_db(samples);
+        self.detect_cutoff_in_spectrum(&spectrum, sample_rate)
+    }
+
+    /// Same as `detect_cutoff`, on a spectrum already produced by
+    /// `compute_power_spectrum_db` so it can be shared with other checks.
+    pub fn detect_cutoff_in_spectrum(&self, spectrum: &[f64], sample_rate: u32) -> Option<f64> {
         let freq_resolution = sample_rate as f64 / self.fft_size as f64;
         let nyquist = sample_rate as f64 / 2.0;
 
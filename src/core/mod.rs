@@ -4,6 +4,7 @@ pub mod analysis;
 pub mod decoder;
 pub mod detector;
 pub mod dsp;
+pub mod tags;
 pub mod visualization;
 
 pub use analysis::{AnalysisConfig, AnalysisResult, DetectionMethod};
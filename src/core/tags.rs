@@ -0,0 +1,532 @@
+// src/core/tags.rs
+//
+// Verdict tag writing. Stores the analysis verdict in the file's own
+// metadata so a media library remembers the result.
+//
+// Symphonia only reads metadata, so FLAC VORBIS_COMMENT blocks are
+// edited directly here. Every key written starts with `AUDIOCHECKR_`,
+// which makes the change reversible: `remove_verdict_tags` strips all
+// of them, drops the comment block again if this module added it, and
+// leaves the rest of the file untouched.
+
+use crate::core::analysis::summary::{compute_quality_score, format_defect_detail, verdict_label};
+use crate::core::analysis::AnalysisResult;
+use anyhow::{bail, Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Prefix shared by every tag key this module writes
+pub const TAG_PREFIX: &str = "AUDIOCHECKR_";
+/// Verdict label, e.g. "Lossless" or "Lossy / Fake"
+pub const VERDICT_TAG: &str = "AUDIOCHECKR_VERDICT";
+/// One-line summary of the detections
+pub const SUMMARY_TAG: &str = "AUDIOCHECKR_SUMMARY";
+/// RFC 3339 timestamp of the analysis
+pub const ANALYZED_TAG: &str = "AUDIOCHECKR_ANALYZED";
+
+const FLAC_MAGIC: &[u8; 4] = b"fLaC";
+const BLOCK_STREAMINFO: u8 = 0;
+const BLOCK_PADDING: u8 = 1;
+const BLOCK_VORBIS_COMMENT: u8 = 4;
+const MAX_BLOCK_LEN: usize = (1 << 24) - 1;
+/// Vendor string prefix of comment blocks this module creates
+const VENDOR_PREFIX: &str = "audiocheckr ";
+
+/// Whether `path` is a format this module can tag (FLAC only)
+pub fn supports_tags(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("flac"))
+        .unwrap_or(false)
+}
+
+/// Build the verdict tags for an analysis result
+pub fn verdict_tags(result: &AnalysisResult) -> Vec<(String, String)> {
+    let genuine = result.is_genuine();
+    let score = compute_quality_score(result);
+
+    let summary = if result.detections.is_empty() {
+        "No defects detected".to_string()
+    } else {
+        result
+            .detections
+            .iter()
+            .map(|d| format_defect_detail(&d.defect_type))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+
+    vec![
+        (
+            VERDICT_TAG.to_string(),
+            verdict_label(genuine, score).to_string(),
+        ),
+        (SUMMARY_TAG.to_string(), summary),
+        (ANALYZED_TAG.to_string(), result.analysis_timestamp.clone()),
+    ]
+}
+
+/// Write the verdict for `result` into the file it was produced from
+pub fn write_verdict_tags(result: &AnalysisResult) -> Result<()> {
+    let tags = verdict_tags(result);
+    update_tags(&result.file_path, |comments| {
+        comments.retain(|c| !is_own_comment(c));
+        comments.extend(tags.iter().map(|(k, v)| format!("{}={}", k, v)));
+    })
+}
+
+/// Remove every `AUDIOCHECKR_*` tag from a file
+pub fn remove_verdict_tags(path: &Path) -> Result<()> {
+    update_tags(path, |comments| comments.retain(|c| !is_own_comment(c)))
+}
+
+/// Read back the `AUDIOCHECKR_*` tags stored in a file
+pub fn read_verdict_tags(path: &Path) -> Result<Vec<(String, String)>> {
+    let data = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let flac = FlacMetadata::parse(&data, path)?;
+
+    let comments = match flac.vorbis_comment() {
+        Some(block) => VorbisComment::parse(&block.data)?.comments,
+        None => Vec::new(),
+    };
+
+    Ok(comments
+        .iter()
+        .filter(|c| is_own_comment(c))
+        .filter_map(|c| c.split_once('='))
+        .map(|(k, v)| (k.to_uppercase(), v.to_string()))
+        .collect())
+}
+
+fn is_own_comment(comment: &str) -> bool {
+    comment
+        .split_once('=')
+        .map(|(k, _)| k.to_uppercase().starts_with(TAG_PREFIX))
+        .unwrap_or(false)
+}
+
+/// Apply `edit` to the file's Vorbis comments and write the result back.
+/// Nothing is written if `edit` leaves the comments unchanged.
+///
+/// Symlinks are followed so the real file is tagged. When the new
+/// metadata fits in the space the old metadata and its PADDING took up,
+/// only the metadata is overwritten in place; otherwise the file is
+/// rewritten through a temporary file that keeps the original
+/// permissions.
+fn update_tags(path: &Path, edit: impl FnOnce(&mut Vec<String>)) -> Result<()> {
+    let path =
+        &fs::canonicalize(path).with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let data = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut flac = FlacMetadata::parse(&data, path)?;
+
+    let mut comment = match flac.vorbis_comment() {
+        Some(block) => VorbisComment::parse(&block.data)?,
+        None => VorbisComment {
+            vendor: format!("{}{}", VENDOR_PREFIX, env!("CARGO_PKG_VERSION")),
+            comments: Vec::new(),
+        },
+    };
+    let before = comment.comments.clone();
+    edit(&mut comment.comments);
+    if comment.comments == before {
+        return Ok(());
+    }
+
+    if comment.comments.is_empty() && comment.vendor.starts_with(VENDOR_PREFIX) {
+        // The block only ever held our tags; take it out again
+        flac.blocks.retain(|b| b.block_type != BLOCK_VORBIS_COMMENT);
+    } else {
+        let block_data = comment.to_bytes();
+        if block_data.len() > MAX_BLOCK_LEN {
+            bail!("Vorbis comment block too large in {}", path.display());
+        }
+
+        match flac
+            .blocks
+            .iter_mut()
+            .find(|b| b.block_type == BLOCK_VORBIS_COMMENT)
+        {
+            Some(block) => block.data = block_data,
+            None => {
+                // STREAMINFO must stay first; put the comments right after it
+                flac.blocks.insert(
+                    1.min(flac.blocks.len()),
+                    MetadataBlock {
+                        block_type: BLOCK_VORBIS_COMMENT,
+                        data: block_data,
+                    },
+                );
+            }
+        }
+    }
+
+    if flac.fit_padding() {
+        write_in_place(path, flac.magic_offset, &flac.metadata_bytes())
+    } else {
+        let mut out = data[..flac.magic_offset].to_vec();
+        out.extend_from_slice(&flac.metadata_bytes());
+        out.extend_from_slice(&data[flac.audio_offset..]);
+        replace_file(path, &out)
+    }
+}
+
+/// Overwrite the metadata region, leaving every other byte of the file alone.
+fn write_in_place(path: &Path, offset: usize, metadata: &[u8]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {} for writing", path.display()))?;
+    file.seek(SeekFrom::Start(offset as u64))?;
+    file.write_all(metadata)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Write `contents` next to `path` and rename it over the original, so a
+/// failure part way through never leaves a truncated file behind.
+fn replace_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let permissions = fs::metadata(path)
+        .with_context(|| format!("Failed to stat {}", path.display()))?
+        .permissions();
+    let tmp_path = path.with_extension("audiocheckr-tmp");
+
+    let result = fs::write(&tmp_path, contents)
+        .and_then(|_| fs::set_permissions(&tmp_path, permissions))
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// FLAC metadata blocks
+// ============================================================================
+
+struct MetadataBlock {
+    block_type: u8,
+    data: Vec<u8>,
+}
+
+struct FlacMetadata {
+    /// Offset of the `fLaC` marker (non-zero when an ID3v2 tag precedes it)
+    magic_offset: usize,
+    blocks: Vec<MetadataBlock>,
+    /// Offset of the first audio frame
+    audio_offset: usize,
+}
+
+impl FlacMetadata {
+    fn parse(data: &[u8], path: &Path) -> Result<Self> {
+        let magic_offset = skip_id3v2(data);
+        if data.len() < magic_offset + 4 || &data[magic_offset..magic_offset + 4] != FLAC_MAGIC {
+            bail!(
+                "Tag writing is only supported for FLAC files: {}",
+                path.display()
+            );
+        }
+
+        let mut pos = magic_offset + 4;
+        let mut blocks = Vec::new();
+
+        loop {
+            if pos + 4 > data.len() {
+                bail!("Truncated FLAC metadata in {}", path.display());
+            }
+            let header = data[pos];
+            let is_last = header & 0x80 != 0;
+            let block_type = header & 0x7F;
+            let len = u32::from_be_bytes([0, data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+            pos += 4;
+
+            if pos + len > data.len() {
+                bail!("Truncated FLAC metadata block in {}", path.display());
+            }
+            blocks.push(MetadataBlock {
+                block_type,
+                data: data[pos..pos + len].to_vec(),
+            });
+            pos += len;
+
+            if is_last {
+                break;
+            }
+        }
+
+        if blocks.first().map(|b| b.block_type) != Some(BLOCK_STREAMINFO) {
+            bail!("FLAC file has no leading STREAMINFO: {}", path.display());
+        }
+
+        Ok(Self {
+            magic_offset,
+            blocks,
+            audio_offset: pos,
+        })
+    }
+
+    fn vorbis_comment(&self) -> Option<&MetadataBlock> {
+        self.blocks
+            .iter()
+            .find(|b| b.block_type == BLOCK_VORBIS_COMMENT)
+    }
+
+    /// Size the PADDING so the metadata ends exactly where the audio
+    /// starts. Returns false if the blocks no longer fit, or would need
+    /// padding the file did not already have; the padding is then left
+    /// as it was.
+    fn fit_padding(&mut self) -> bool {
+        let had_padding = self.blocks.iter().any(|b| b.block_type == BLOCK_PADDING);
+        let available = self.audio_offset - self.magic_offset - FLAC_MAGIC.len();
+        let used: usize = self
+            .blocks
+            .iter()
+            .filter(|b| b.block_type != BLOCK_PADDING)
+            .map(|b| 4 + b.data.len())
+            .sum();
+
+        let padding = match available.checked_sub(used) {
+            Some(0) => None,
+            Some(free) if had_padding && (4..=MAX_BLOCK_LEN + 4).contains(&free) => Some(free - 4),
+            _ => return false,
+        };
+
+        self.blocks.retain(|b| b.block_type != BLOCK_PADDING);
+        if let Some(len) = padding {
+            self.blocks.push(MetadataBlock {
+                block_type: BLOCK_PADDING,
+                data: vec![0; len],
+            });
+        }
+        true
+    }
+
+    /// Serialize the `fLaC` marker and all metadata blocks
+    fn metadata_bytes(&self) -> Vec<u8> {
+        let mut out = FLAC_MAGIC.to_vec();
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let last = if i + 1 == self.blocks.len() { 0x80 } else { 0 };
+            out.push(last | block.block_type);
+            out.extend_from_slice(&(block.data.len() as u32).to_be_bytes()[1..]);
+            out.extend_from_slice(&block.data);
+        }
+
+        out
+    }
+}
+
+/// Length of a leading ID3v2 tag, or 0 if there is none
+fn skip_id3v2(data: &[u8]) -> usize {
+    if data.len() < 10 || &data[..3] != b"ID3" {
+        return 0;
+    }
+    // Syncsafe 28-bit size, plus 10-byte header and optional 10-byte footer
+    let size = data[6..10]
+        .iter()
+        .fold(0usize, |acc, &b| (acc << 7) | (b & 0x7F) as usize);
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+    (10 + size + footer).min(data.len())
+}
+
+// ============================================================================
+// Vorbis comment block
+// ============================================================================
+
+struct VorbisComment {
+    vendor: String,
+    comments: Vec<String>,
+}
+
+impl VorbisComment {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut pos = 0usize;
+        let vendor = read_string(data, &mut pos)?;
+        let count = read_u32_le(data, &mut pos)? as usize;
+
+        let mut comments = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            comments.push(read_string(data, &mut pos)?);
+        }
+
+        Ok(Self { vendor, comments })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.vendor.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.vendor.as_bytes());
+        out.extend_from_slice(&(self.comments.len() as u32).to_le_bytes());
+        for c in &self.comments {
+            out.extend_from_slice(&(c.len() as u32).to_le_bytes());
+            out.extend_from_slice(c.as_bytes());
+        }
+        out
+    }
+}
+
+fn read_u32_le(data: &[u8], pos: &mut usize) -> Result<u32> {
+    let bytes = data
+        .get(*pos..*pos + 4)
+        .context("Truncated Vorbis comment block")?;
+    *pos += 4;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_string(data: &[u8], pos: &mut usize) -> Result<String> {
+    let len = read_u32_le(data, pos)? as usize;
+    let bytes = data
+        .get(*pos..*pos + len)
+        .context("Truncated Vorbis comment block")?;
+    *pos += len;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::analysis::summary;
+    use std::path::PathBuf;
+
+    const FAKE_FRAMES: &[u8] = &[0xFF, 0xF8, 0x69, 0x08, 0x00, 0x13, 0xAA, 0x55];
+
+    /// Minimal FLAC: marker, STREAMINFO, optional PADDING, and a few
+    /// opaque frame bytes
+    fn write_temp_flac_padded(name: &str, padding: Option<u8>) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "audiocheckr_tags_{}_{}.flac",
+            name,
+            std::process::id()
+        ));
+        let mut data = FLAC_MAGIC.to_vec();
+        let last = if padding.is_none() { 0x80 } else { 0 };
+        data.push(last | BLOCK_STREAMINFO);
+        data.extend_from_slice(&[0, 0, 34]);
+        data.extend_from_slice(&[0u8; 34]);
+        if let Some(kib) = padding {
+            data.push(0x80 | BLOCK_PADDING);
+            data.extend_from_slice(&[0, kib * 4, 0]);
+            data.resize(data.len() + kib as usize * 1024, 0);
+        }
+        data.extend_from_slice(FAKE_FRAMES);
+        fs::write(&path, data).unwrap();
+        path
+    }
+
+    fn write_temp_flac(name: &str) -> PathBuf {
+        write_temp_flac_padded(name, None)
+    }
+
+    fn transcoded_result(path: PathBuf) -> AnalysisResult {
+        AnalysisResult {
+            file_path: path,
+            ..summary::tests::transcoded_result()
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_back_verdict_tag() {
+        let path = write_temp_flac("roundtrip");
+        let result = transcoded_result(path.clone());
+
+        write_verdict_tags(&result).unwrap();
+        let tags = read_verdict_tags(&path).unwrap();
+
+        let verdict = tags.iter().find(|(k, _)| k == VERDICT_TAG).map(|(_, v)| v);
+        assert_eq!(verdict.map(String::as_str), Some("Lossy / Fake"));
+        let summary = tags.iter().find(|(k, _)| k == SUMMARY_TAG).map(|(_, v)| v);
+        assert!(summary.unwrap().contains("cutoff 16000 Hz"));
+
+        // Rewriting replaces rather than duplicates
+        write_verdict_tags(&result).unwrap();
+        assert_eq!(read_verdict_tags(&path).unwrap().len(), tags.len());
+
+        // Audio bytes are untouched
+        let data = fs::read(&path).unwrap();
+        assert!(data.ends_with(FAKE_FRAMES));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_remove_verdict_tags() {
+        let path = write_temp_flac("remove");
+        let original = fs::read(&path).unwrap();
+
+        // Removing from an untagged file is a no-op
+        remove_verdict_tags(&path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), original);
+
+        write_verdict_tags(&transcoded_result(path.clone())).unwrap();
+        assert_ne!(fs::read(&path).unwrap(), original);
+
+        remove_verdict_tags(&path).unwrap();
+        assert!(read_verdict_tags(&path).unwrap().is_empty());
+        assert_eq!(fs::read(&path).unwrap(), original);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_padding_absorbs_tags() {
+        let path = write_temp_flac_padded("padding", Some(1));
+        let original = fs::read(&path).unwrap();
+        let len = original.len() as u64;
+
+        write_verdict_tags(&transcoded_result(path.clone())).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+        assert!(!read_verdict_tags(&path).unwrap().is_empty());
+
+        remove_verdict_tags(&path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), original);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_target_tagged_and_permissions_kept() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let target = write_temp_flac("symlink_target");
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o640)).unwrap();
+        let link =
+            target.with_file_name(format!("audiocheckr_tags_link_{}.flac", std::process::id()));
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_verdict_tags(&transcoded_result(link.clone())).unwrap();
+
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(!read_verdict_tags(&target).unwrap().is_empty());
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+
+        let _ = fs::remove_file(&link);
+        let _ = fs::remove_file(&target);
+    }
+
+    #[test]
+    fn test_supports_tags_only_flac() {
+        assert!(supports_tags(Path::new("album/track.flac")));
+        assert!(supports_tags(Path::new("TRACK.FLAC")));
+        assert!(!supports_tags(Path::new("track.wav")));
+        assert!(!supports_tags(Path::new("track")));
+    }
+
+    #[test]
+    fn test_non_flac_rejected() {
+        let path =
+            std::env::temp_dir().join(format!("audiocheckr_tags_wav_{}.wav", std::process::id()));
+        fs::write(&path, b"RIFF\0\0\0\0WAVE").unwrap();
+
+        assert!(remove_verdict_tags(&path).is_err());
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -10,7 +10,7 @@ pub mod core;
 pub use core::analysis::{
     AnalysisConfig, AnalysisResult, DefectType, Detection, DetectionMethod, DynamicRangeAnalyzer,
     DynamicRangeResult, DynamicRangeVerdict, MfccAnalyzer, MfccConfig, MfccFingerprint, MfccResult,
-    QualityMetrics, QualityScore, Severity, TemporalDistribution,
+    QualityMetrics, QualityScore, SegmentVerdict, Severity, TemporalDistribution, Verbosity,
 };
 pub use core::detector::AudioDetector;
 pub use core::dsp::{SpectralAnalyzer, WindowFunction};
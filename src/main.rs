@@ -3,7 +3,7 @@
 //! Detects fake lossless audio files through spectral analysis, bit depth
 //! verification, MQA detection, and other sophisticated techniques.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Parser;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
@@ -18,6 +18,7 @@ use cli::output::OutputHandler;
 use core::analysis::{AnalysisConfig, AnalysisResult, AnalysisSensitivity};
 use core::decoder::{decode_audio, extract_mono};
 use core::detector::AudioDetector;
+use core::tags::{remove_verdict_tags, supports_tags, write_verdict_tags};
 use core::visualization::{generate_mel_spectrogram, SpectrogramConfig};
 
 fn main() -> Result<()> {
@@ -39,6 +40,34 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.write_tags || args.remove_tags {
+        let flag = if args.write_tags {
+            "--write-tags"
+        } else {
+            "--remove-tags"
+        };
+        let untaggable = files.iter().filter(|f| !supports_tags(f)).count();
+        if untaggable == files.len() {
+            bail!("{} only supports FLAC files", flag);
+        }
+        if untaggable > 0 {
+            eprintln!(
+                "Note: {} non-FLAC file(s) will not be tagged (tagging is FLAC only)",
+                untaggable
+            );
+        }
+    }
+
+    if args.remove_tags {
+        for file in files.iter().filter(|f| supports_tags(f)) {
+            match remove_verdict_tags(file) {
+                Ok(()) => eprintln!("Removed tags from {}", file.display()),
+                Err(e) => eprintln!("Warning: {}", e),
+            }
+        }
+        return Ok(());
+    }
+
     let config = AnalysisConfig {
         fft_size: 8192,
         hop_size: 2048,
@@ -46,6 +75,9 @@ fn main() -> Result<()> {
         enable_mqa: args.mqa,
         enable_clipping: args.clipping,
         enable_enf: args.enf,
+        enable_noise_floor: args.noise_floor,
+        enable_spectral_holes: args.spectral_holes,
+        segment_duration: args.segment_duration,
         enable_mfcc: args.mfcc,
         genre_profile: args.genre.map(|g| format!("{:?}", g)),
         sensitivity: match args.sensitivity {
@@ -124,6 +156,16 @@ fn main() -> Result<()> {
                         );
                     }
                 }
+
+                if args.write_tags && supports_tags(&analysis.file_path) {
+                    if let Err(e) = write_verdict_tags(&analysis) {
+                        eprintln!(
+                            "Warning: tag writing failed for {}: {}",
+                            analysis.file_path.display(),
+                            e
+                        );
+                    }
+                }
             }
             Err(e) => {
                 error_count += 1;